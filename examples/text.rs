@@ -0,0 +1,23 @@
+extern crate nest;
+
+use nest::*;
+use std::time::Instant;
+
+fn main() {
+    let mut app = Window::new("Text", 640, 480).expect("error: failed to open window");
+    let font = Font::open("examples/DejaVuSansMono.ttf").unwrap();
+    let poem = "Two roads diverged in a yellow wood, and sorry I could not travel both";
+
+    let start = Instant::now();
+
+    while !app.poll_events().any(|e| e == Event::Closed) {
+        app.draw(
+            text(&font, "nest", 0.3)
+                .align(Align::Center)
+                .rotate(start.elapsed().to_secs().sin() * 0.2)
+                .translate([0.0, 0.5])
+                .recolor(Color::YELLOW)
+                .combine(text(&font, poem, 0.1).wrap(1.6).align(Align::Center)),
+        );
+    }
+}
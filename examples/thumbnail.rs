@@ -0,0 +1,16 @@
+extern crate nest;
+
+use nest::*;
+use std::f32::consts::PI;
+
+fn main() {
+    // No window is needed to render the flower from the demo example.
+    let petal_texture = Texture::open("examples/petal.png").unwrap();
+    let petal = image_w(petal_texture, 0.4).translate([0.3, 0.0]);
+
+    let flower = (0usize..6)
+        .flat_map(|i| petal.rotate(i as f32 / 6.0 * 2.0 * PI))
+        .collect::<Vec<_>>();
+
+    render_to_png(flower, 256, 256, Color::BLACK, "flower.png").expect("error: failed to save image");
+}
@@ -0,0 +1,167 @@
+use *;
+
+/// What an `Animation` does after its last frame.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LoopMode {
+    /// Stay on the last frame.
+    Once,
+    /// Start over from the first frame.
+    Loop,
+    /// Play the frames backwards to the first one, then forwards again.
+    PingPong,
+}
+
+/// A sequence of texture regions which are shown one after another, such as the frames of a
+/// walking character in a `SpriteSheet`.
+///
+/// # Example
+/// ```rust,no_run
+/// use nest::*;
+/// use std::time::Instant;
+///
+/// let mut app = Window::new("Example", 640, 480).unwrap();
+/// let sheet = SpriteSheet::grid(app.load_texture("walk.png").unwrap(), [32, 32]);
+/// // Show every frame for a tenth of a second.
+/// let walk = Animation::from_frames(sheet.frames().iter().cloned(), 0.1).mode(LoopMode::PingPong);
+///
+/// let start = Instant::now();
+/// while !app.poll_events().any(|e| e == Event::Closed) {
+///     app.draw(walk.image([-0.2, -0.2], [0.2, 0.2], start.elapsed()));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Animation {
+    frames: Vec<(TextureRegion, f32)>,
+    mode: LoopMode,
+}
+
+impl Default for Animation {
+    fn default() -> Animation {
+        Animation::new()
+    }
+}
+
+impl Animation {
+    /// Create an animation without any frames which loops.
+    #[inline]
+    pub fn new() -> Animation {
+        Animation {
+            frames: Vec::new(),
+            mode: LoopMode::Loop,
+        }
+    }
+
+    /// Create an animation which shows each of the `frames` for `duration`.
+    pub fn from_frames<I, R, D>(frames: I, duration: D) -> Animation
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<TextureRegion>,
+        D: ToSeconds,
+    {
+        let duration = duration.to_secs();
+        Animation {
+            frames: frames.into_iter().map(|f| (f.into(), duration)).collect(),
+            mode: LoopMode::Loop,
+        }
+    }
+
+    /// Add a frame which is shown for `duration`.
+    #[inline]
+    pub fn frame<R: Into<TextureRegion>, D: ToSeconds>(mut self, region: R, duration: D) -> Animation {
+        self.frames.push((region.into(), duration.to_secs()));
+        self
+    }
+
+    /// Choose what happens after the last frame. The default is `LoopMode::Loop`.
+    #[inline]
+    pub fn mode(mut self, mode: LoopMode) -> Animation {
+        self.mode = mode;
+        self
+    }
+
+    /// The number of frames.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns true if the animation has no frames.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// The time in seconds it takes to play all frames once. With `LoopMode::PingPong` this
+    /// includes playing them backwards.
+    pub fn duration(&self) -> f32 {
+        let forward: f32 = self.frames.iter().map(|f| f.1).sum();
+        match self.mode {
+            LoopMode::PingPong if self.frames.len() > 2 => {
+                let ends = self.frames[0].1 + self.frames[self.frames.len() - 1].1;
+                2.0 * forward - ends
+            }
+            _ => forward,
+        }
+    }
+
+    /// Returns true if the animation has stopped on its last frame at `time`, which is only
+    /// possible with `LoopMode::Once`.
+    pub fn is_finished<T: ToSeconds>(&self, time: T) -> bool {
+        self.mode == LoopMode::Once && time.to_secs() >= self.duration()
+    }
+
+    /// The index of the frame which is shown at `time`.
+    pub fn index<T: ToSeconds>(&self, time: T) -> Option<usize> {
+        let n = self.frames.len();
+        if n == 0 {
+            return None;
+        }
+        let total = self.duration();
+        let time = time.to_secs().max(0.0);
+        if !(total > 0.0) {
+            return Some(if self.mode == LoopMode::Once { n - 1 } else { 0 });
+        }
+        let mut t = match self.mode {
+            LoopMode::Once if time >= total => return Some(n - 1),
+            LoopMode::Once => time,
+            LoopMode::Loop | LoopMode::PingPong => time % total,
+        };
+        // Going backwards skips the last and first frames, which end the forward pass and start
+        // the next one.
+        let backward = match self.mode {
+            LoopMode::PingPong => 1..n.saturating_sub(1),
+            _ => 0..0,
+        };
+        let mut last = 0;
+        for i in (0..n).chain(backward.rev()) {
+            if t < self.frames[i].1 {
+                return Some(i);
+            }
+            t -= self.frames[i].1;
+            last = i;
+        }
+        // Rounding errors can leave a little time after the last frame.
+        Some(last)
+    }
+
+    /// The frame which is shown at `time`.
+    #[inline]
+    pub fn region<T: ToSeconds>(&self, time: T) -> Option<&TextureRegion> {
+        self.index(time).map(|i| &self.frames[i].0)
+    }
+
+    /// Takes two points and the time since the animation started and draws the frame shown at
+    /// that time on the rectangle specified by the two points.
+    ///
+    /// # Panics
+    /// Panics if the animation has no frames.
+    pub fn image<A, B, T>(&self, first: A, second: B, time: T) -> Image
+    where
+        A: Into<cgm::Point2<f32>>,
+        B: Into<cgm::Point2<f32>>,
+        T: ToSeconds,
+    {
+        let region = self.region(time).expect("animation has no frames").clone();
+        image_region(first, second, region)
+    }
+}
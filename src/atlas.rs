@@ -0,0 +1,154 @@
+use std::cmp::Reverse;
+use std::path;
+use *;
+
+/// Packs many images into a few large textures, so that they can be drawn in a single batch.
+///
+/// Every image gets a border of its own edge pixels, so that neighbours in the atlas do not bleed
+/// into each other when the textures are sampled.
+///
+/// # Example
+/// ```rust,no_run
+/// use nest::*;
+/// let mut app = Window::new("Example", 640, 480).unwrap();
+/// let mut atlas = TextureAtlas::new();
+/// let petal = atlas.add_file("examples/petal.png").unwrap();
+/// let city = atlas.add_file("examples/city.jpg").unwrap();
+/// let regions = atlas.build().unwrap();
+/// app.draw(image_w(regions[petal].clone(), 0.5).combine(image_w(regions[city].clone(), 0.3)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct TextureAtlas {
+    images: Vec<img::RgbaImage>,
+    max_size: u32,
+    padding: u32,
+}
+
+impl Default for TextureAtlas {
+    fn default() -> TextureAtlas {
+        TextureAtlas::new()
+    }
+}
+
+impl TextureAtlas {
+    /// Create an empty atlas with textures of up to 2048 by 2048 pixels and a border of 1 pixel
+    /// around every image.
+    #[inline]
+    pub fn new() -> TextureAtlas {
+        TextureAtlas {
+            images: Vec::new(),
+            max_size: 2048,
+            padding: 1,
+        }
+    }
+
+    /// Limit the width and height of the textures to `size` pixels.
+    #[inline]
+    pub fn max_size(mut self, size: u32) -> TextureAtlas {
+        self.max_size = size;
+        self
+    }
+
+    /// Put a border of `padding` pixels around every image.
+    #[inline]
+    pub fn padding(mut self, padding: u32) -> TextureAtlas {
+        self.padding = padding;
+        self
+    }
+
+    /// Add an image and get the index of its region in the result of `TextureAtlas::build()`.
+    pub fn add(&mut self, image: img::RgbaImage) -> usize {
+        self.images.push(image);
+        self.images.len() - 1
+    }
+
+    /// Add the image in the file at `path`, like `TextureAtlas::add()`.
+    pub fn add_file<P: AsRef<path::Path>>(&mut self, path: P) -> Result<usize> {
+        Ok(self.add(img::open(path)?.to_rgba()))
+    }
+
+    /// The number of images which have been added.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.images.len()
+    }
+
+    /// Returns true if no images have been added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.images.is_empty()
+    }
+
+    /// Pack the images into textures and get the region of each image, in the order they were
+    /// added in.
+    ///
+    /// The images are sorted by height and placed in rows. A new texture is started whenever one
+    /// is full.
+    pub fn build(self) -> Result<Vec<TextureRegion>> {
+        let pad = self.padding;
+        let max = self.max_size;
+        for image in &self.images {
+            let (w, h) = image.dimensions();
+            if w + 2 * pad > max || h + 2 * pad > max {
+                bail!(ErrorKind::ImageTooLarge(w, h));
+            }
+        }
+
+        let mut order: Vec<usize> = (0..self.images.len()).collect();
+        order.sort_by_key(|&i| Reverse(self.images[i].height()));
+
+        // The page and position of each image, and the used size of each page.
+        let mut places = vec![(0, [0, 0]); self.images.len()];
+        let mut pages = vec![[0u32, 0u32]];
+        let (mut x, mut y, mut row) = (0, 0, 0);
+        for &i in &order {
+            let (w, h) = self.images[i].dimensions();
+            let (w, h) = (w + 2 * pad, h + 2 * pad);
+            if x + w > max {
+                x = 0;
+                y += row;
+                row = 0;
+            }
+            if y + h > max {
+                pages.push([0, 0]);
+                x = 0;
+                y = 0;
+                row = 0;
+            }
+            let page = pages.len() - 1;
+            places[i] = (page, [x + pad, y + pad]);
+            pages[page] = [pages[page][0].max(x + w), pages[page][1].max(y + h)];
+            x += w;
+            row = row.max(h);
+        }
+
+        let mut images: Vec<img::RgbaImage> = pages
+            .iter()
+            .map(|size| img::RgbaImage::new(size[0].max(1), size[1].max(1)))
+            .collect();
+        for (image, &(page, pos)) in self.images.iter().zip(&places) {
+            let (w, h) = image.dimensions();
+            if w == 0 || h == 0 {
+                continue;
+            }
+            // Copy the image and extrude its edges into the padding around it.
+            let target = &mut images[page];
+            for ty in pos[1] - pad..pos[1] + h + pad {
+                for tx in pos[0] - pad..pos[0] + w + pad {
+                    let sx = (tx as i64 - pos[0] as i64).max(0).min(w as i64 - 1) as u32;
+                    let sy = (ty as i64 - pos[1] as i64).max(0).min(h as i64 - 1) as u32;
+                    target.put_pixel(tx, ty, *image.get_pixel(sx, sy));
+                }
+            }
+        }
+
+        let textures: Vec<Texture> = images.into_iter().map(Texture::from_image).collect();
+        Ok(self.images
+            .iter()
+            .zip(places)
+            .map(|(image, (page, pos))| {
+                TextureRegion::from_pixels(textures[page].clone(), pos, [image.width(), image.height()])
+            })
+            .collect())
+    }
+}
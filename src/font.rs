@@ -0,0 +1,312 @@
+use rusttype::{self, Codepoint, FontCollection, GlyphId, Scale};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path;
+use std::rc::Rc;
+use *;
+
+/// The default height in pixels at which glyphs are rasterized.
+const DEFAULT_RESOLUTION: f32 = 64.0;
+/// The width of a new glyph atlas in pixels.
+const ATLAS_WIDTH: u32 = 1024;
+/// The height of a new glyph atlas in pixels. Fonts which need more room get a larger atlas.
+const ATLAS_HEIGHT: u32 = 1024;
+
+/// A font with TrueType (glyf) outlines which can be drawn by `text()`. OpenType fonts with CFF
+/// outlines are not supported.
+///
+/// Glyphs are rasterized the first time they are drawn and cached in an atlas texture which all
+/// text drawn with the font shares, so the text of a frame is usually drawn in a single batch.
+/// New glyphs are written into the atlas with `Texture::update()`, so only their pixels are
+/// uploaded. Cloning a `Font` is cheap and clones share the same atlas.
+#[derive(Clone)]
+pub struct Font(Rc<FontData>);
+
+struct FontData {
+    font: rusttype::Font<'static>,
+    resolution: f32,
+    atlas: RefCell<Atlas>,
+}
+
+/// Where a rasterized glyph is in the atlas.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct AtlasGlyph {
+    /// The position of the glyph in the atlas in pixels.
+    pub(crate) min: [u32; 2],
+    /// The size of the glyph in pixels.
+    pub(crate) size: [u32; 2],
+    /// The position of the top left corner of the glyph relative to its origin on the baseline,
+    /// in pixels with y pointing down.
+    pub(crate) offset: [i32; 2],
+}
+
+struct Atlas {
+    texture: Texture,
+    /// Glyphs without any pixels, like spaces, are stored as `None`.
+    glyphs: HashMap<u32, Option<AtlasGlyph>>,
+    /// Where the next glyph is placed.
+    cursor: [u32; 2],
+    /// The height of the tallest glyph in the current row.
+    row_height: u32,
+}
+
+impl Font {
+    /// Load a font with TrueType (glyf) outlines from the contents of a font file.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Font> {
+        let font = match FontCollection::from_bytes(bytes).into_font() {
+            Some(font) => font,
+            None => bail!(ErrorKind::InvalidFont("not a single font with TrueType outlines")),
+        };
+        Ok(Font::with_font(font, DEFAULT_RESOLUTION))
+    }
+
+    /// Load a font with TrueType (glyf) outlines from a file. This does not require a `Window`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use nest::*;
+    /// let font = Font::open("examples/DejaVuSansMono.ttf").unwrap();
+    /// ```
+    pub fn open<P: AsRef<path::Path>>(path: P) -> Result<Font> {
+        let mut buf = Vec::new();
+        File::open(path)?.read_to_end(&mut buf)?;
+        Font::from_bytes(buf)
+    }
+
+    /// Rasterize glyphs `pixels` high instead of the default of 64. Text drawn larger than this
+    /// on the screen becomes blurry. The returned font has its own atlas.
+    pub fn resolution(&self, pixels: f32) -> Font {
+        Font::with_font(self.0.font.clone(), pixels)
+    }
+
+    /// The width of the first line of `string` when drawn with a height of `size`.
+    pub fn width(&self, string: &str, size: f32) -> f32 {
+        let line = string.lines().next().unwrap_or("");
+        self.pixel_width(line) * size / self.0.resolution
+    }
+
+    fn with_font(font: rusttype::Font<'static>, resolution: f32) -> Font {
+        Font(Rc::new(FontData {
+            font: font,
+            resolution: resolution,
+            atlas: RefCell::new(Atlas {
+                texture: Texture::from_image(img::RgbaImage::new(ATLAS_WIDTH, ATLAS_HEIGHT)),
+                glyphs: HashMap::new(),
+                cursor: [1, 1],
+                row_height: 0,
+            }),
+        }))
+    }
+
+    /// The height in pixels at which glyphs are rasterized.
+    #[inline]
+    pub(crate) fn pixels(&self) -> f32 {
+        self.0.resolution
+    }
+
+    #[inline]
+    pub(crate) fn scale(&self) -> Scale {
+        Scale::uniform(self.0.resolution)
+    }
+
+    /// The distance between the baselines of two lines, in pixels.
+    pub(crate) fn line_height(&self) -> f32 {
+        let v = self.0.font.v_metrics(self.scale());
+        v.ascent - v.descent + v.line_gap
+    }
+
+    /// The glyph which draws `c`, if the font has one.
+    #[inline]
+    pub(crate) fn glyph_id(&self, c: char) -> Option<GlyphId> {
+        self.0.font.glyph(Codepoint(c as u32)).map(|g| g.id())
+    }
+
+    /// How far to move after drawing `glyph`, in pixels.
+    pub(crate) fn advance(&self, glyph: GlyphId) -> f32 {
+        match self.0.font.glyph(glyph) {
+            Some(g) => g.scaled(self.scale()).h_metrics().advance_width,
+            None => 0.0,
+        }
+    }
+
+    /// The adjustment of the distance between two glyphs, in pixels.
+    #[inline]
+    pub(crate) fn kerning(&self, first: GlyphId, second: GlyphId) -> f32 {
+        self.0.font.pair_kerning(self.scale(), first, second)
+    }
+
+    /// The width of a single line, in pixels.
+    pub(crate) fn pixel_width(&self, line: &str) -> f32 {
+        let mut width = 0.0;
+        let mut last = None;
+        for id in line.chars().filter_map(|c| self.glyph_id(c)) {
+            if let Some(last) = last {
+                width += self.kerning(last, id);
+            }
+            width += self.advance(id);
+            last = Some(id);
+        }
+        width
+    }
+
+    /// Find `glyph` in the atlas, rasterizing it first if it is not there yet. Returns `None` for
+    /// glyphs without any pixels.
+    pub(crate) fn atlas_glyph(&self, glyph: GlyphId) -> Option<AtlasGlyph> {
+        let mut atlas = self.0.atlas.borrow_mut();
+        if let Some(cached) = atlas.glyphs.get(&glyph.0) {
+            return *cached;
+        }
+        let placed = self.0.font.glyph(glyph).and_then(|g| {
+            let g = g.scaled(self.scale()).positioned(rusttype::point(0.0, 0.0));
+            g.pixel_bounding_box().map(|bb| (g, bb))
+        });
+        let (g, bb) = match placed {
+            Some(placed) => placed,
+            None => {
+                atlas.glyphs.insert(glyph.0, None);
+                return None;
+            }
+        };
+
+        let size = [bb.width() as u32, bb.height() as u32];
+        // Glyphs are placed in rows, with a pixel of space around them so that neighbours do not
+        // bleed into each other when the atlas is sampled.
+        if atlas.cursor[0] > 1 && atlas.cursor[0] + size[0] + 1 > atlas.texture.width() {
+            atlas.cursor = [1, atlas.cursor[1] + atlas.row_height + 1];
+            atlas.row_height = 0;
+        }
+        let min = atlas.cursor;
+        let needed = [min[0] + size[0] + 1, min[1] + size[1] + 1];
+        if needed[0] > atlas.texture.width() || needed[1] > atlas.texture.height() {
+            // The atlas is full, or the glyph is wider than it, so its glyphs are copied into a
+            // larger one. Textures which were handed out before keep their pixels, they are still
+            // correct for the glyphs which they were used with.
+            let (mut width, mut height) = (atlas.texture.width(), atlas.texture.height());
+            while width < needed[0] {
+                width *= 2;
+            }
+            while height < needed[1] {
+                height *= 2;
+            }
+            let mut image = img::RgbaImage::new(width, height);
+            for (x, y, pixel) in atlas.texture.image().enumerate_pixels() {
+                image.put_pixel(x, y, *pixel);
+            }
+            atlas.texture = Texture::from_image(image);
+        }
+        let mut image = img::RgbaImage::from_pixel(size[0], size[1], img::Rgba { data: [255, 255, 255, 0] });
+        g.draw(|x, y, coverage| {
+            if x < size[0] && y < size[1] {
+                let alpha = (coverage.max(0.0).min(1.0) * 255.0).round() as u8;
+                image.put_pixel(x, y, img::Rgba { data: [255, 255, 255, alpha] });
+            }
+        });
+        atlas.texture.update(min, &image).expect("glyphs are placed inside the atlas");
+        atlas.cursor[0] += size[0] + 1;
+        atlas.row_height = atlas.row_height.max(size[1]);
+
+        let placed = AtlasGlyph {
+            min: min,
+            size: size,
+            offset: [bb.min.x, bb.min.y],
+        };
+        atlas.glyphs.insert(glyph.0, Some(placed));
+        Some(placed)
+    }
+
+    /// The atlas texture with all glyphs rasterized so far.
+    #[inline]
+    pub(crate) fn texture(&self) -> Texture {
+        self.0.atlas.borrow().texture.clone()
+    }
+}
+
+impl fmt::Debug for Font {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Font")
+            .field("resolution", &self.0.resolution)
+            .field("glyphs", &self.0.atlas.borrow().glyphs.len())
+            .finish()
+    }
+}
+
+/// A fixed-width font whose glyphs are drawn in a grid of equally sized cells in one texture.
+///
+/// Cells are numbered from left to right and then from top to bottom. By default they hold
+/// consecutive characters, starting with the one passed to `BitmapFont::new()`. A different order
+/// can be given with `BitmapFont::char_map()`.
+///
+/// # Example
+/// ```rust,no_run
+/// # use nest::*;
+/// let mut app = Window::new("Example", 640, 480).unwrap();
+/// // 8x8 pixel cells with the printable ASCII characters, starting with a space.
+/// let font = BitmapFont::new(app.load_texture("font.png").unwrap(), [8, 8], ' ');
+/// app.draw(bitmap_text(&font, "HIGH SCORE", 0.1));
+/// ```
+#[derive(Clone, Debug)]
+pub struct BitmapFont {
+    texture: Texture,
+    cell: [u32; 2],
+    first: char,
+    chars: Option<HashMap<char, u32>>,
+}
+
+impl BitmapFont {
+    /// Create a font from a `texture` divided into cells of `cell` pixels, where the first cell
+    /// holds the character `first` and the rest follow in order.
+    pub fn new<T: Into<Texture>, C: Into<[u32; 2]>>(texture: T, cell: C, first: char) -> BitmapFont {
+        let cell = cell.into();
+        BitmapFont {
+            texture: texture.into(),
+            cell: [cell[0].max(1), cell[1].max(1)],
+            first: first,
+            chars: None,
+        }
+    }
+
+    /// Use the cells for the characters of `chars` in order instead.
+    pub fn char_map(mut self, chars: &str) -> BitmapFont {
+        self.chars = Some(chars.chars().enumerate().map(|(i, c)| (c, i as u32)).collect());
+        self
+    }
+
+    /// The texture which holds the glyphs.
+    #[inline]
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// The size of a cell in pixels.
+    #[inline]
+    pub fn cell(&self) -> [u32; 2] {
+        self.cell
+    }
+
+    /// The width of a glyph drawn `size` high.
+    #[inline]
+    pub fn advance(&self, size: f32) -> f32 {
+        size * self.cell[0] as f32 / self.cell[1] as f32
+    }
+
+    /// The cell of `c`, if the texture has one.
+    pub fn region(&self, c: char) -> Option<TextureRegion> {
+        let index = match self.chars {
+            Some(ref chars) => *chars.get(&c)?,
+            None => (c as u32).checked_sub(self.first as u32)?,
+        };
+        let columns = self.texture.width() / self.cell[0];
+        let rows = self.texture.height() / self.cell[1];
+        if columns == 0 || index >= columns * rows {
+            return None;
+        }
+        Some(TextureRegion::from_pixels(
+            self.texture.clone(),
+            [index % columns * self.cell[0], index / columns * self.cell[1]],
+            self.cell,
+        ))
+    }
+}
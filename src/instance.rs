@@ -0,0 +1,153 @@
+use std::ops::Range;
+use *;
+
+/// A transformation and a color to draw a shape or a `Mesh` with.
+///
+/// The transformations are applied in the order they are called in, like the combinators of a
+/// `Shape`, but they are done by the GPU.
+///
+/// # Example
+/// ```rust,no_run
+/// use nest::*;
+/// let mut app = Window::new("Example", 640, 480).unwrap();
+/// // A row of ten squares which get darker to the right.
+/// let squares: Vec<Instance> = (0..10)
+///     .map(|i| Instance::new().translate([i as f32 * 0.2 - 0.9, 0.0]).mul_color(Color::WHITE.scale(1.0 - i as f32 * 0.1)))
+///     .collect();
+/// app.frame().draw_instanced(rect([-0.05, -0.05], [0.05, 0.05]), &squares);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Instance {
+    transform: Transform,
+    color: Color,
+}
+
+impl Default for Instance {
+    fn default() -> Instance {
+        Instance::new()
+    }
+}
+
+impl Instance {
+    /// An instance which leaves the shape as it is.
+    #[inline]
+    pub fn new() -> Instance {
+        Instance {
+            transform: Transform::IDENTITY,
+            color: Color::WHITE,
+        }
+    }
+
+    /// Translate the shape using a `vector` which represents the direction and magnitude to
+    /// translate it by.
+    #[inline]
+    pub fn translate<V: Into<cgm::Vector2<f32>>>(self, vector: V) -> Instance {
+        self.then(Transform::translation(vector))
+    }
+
+    /// Rotate the shape around the origin using an angle in radians.
+    #[inline]
+    pub fn rotate(self, angle: f32) -> Instance {
+        self.then(Transform::rotation(angle))
+    }
+
+    /// Scale the shape using a `vector` which represents the magnitude to scale it by.
+    #[inline]
+    pub fn scale_both<V: Into<cgm::Vector2<f32>>>(self, scale: V) -> Instance {
+        self.then(Transform::scaling(scale))
+    }
+
+    /// Scale the shape in both dimensions by `scale`.
+    #[inline]
+    pub fn scale(self, scale: f32) -> Instance {
+        self.scale_both([scale, scale])
+    }
+
+    /// Transform the shape with an affine `Transform`, which can also skew, shear or reflect it.
+    #[inline]
+    pub fn transform(self, transform: Transform) -> Instance {
+        self.then(transform)
+    }
+
+    /// Multiply all of the colors in the shape component-wise with the passed color.
+    #[inline]
+    pub fn mul_color<C: Into<Color>>(mut self, color: C) -> Instance {
+        self.color = self.color.multiply(color);
+        self
+    }
+
+    /// The transformation which is applied to the shape.
+    #[inline]
+    pub fn transformation(&self) -> Transform {
+        self.transform
+    }
+
+    /// The color the shape is multiplied with.
+    #[inline]
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    #[inline]
+    fn then(mut self, transform: Transform) -> Instance {
+        self.transform = self.transform.then(transform);
+        self
+    }
+
+    /// The transformation as a column-major matrix for the shaders.
+    #[inline]
+    pub(crate) fn matrix(&self) -> [[f32; 3]; 3] {
+        self.transform.matrix()
+    }
+
+    /// Transform a triangle on the CPU like the shaders do.
+    pub(crate) fn apply(&self, rtri: &RendTri) -> RendTri {
+        let mut rtri = rtri.clone();
+        for p in &mut rtri.tri.positions.0 {
+            *p = self.transform.apply(*p).into();
+        }
+        rtri.tri.color = Color(rtri.tri.color).multiply(self.color).0;
+        rtri
+    }
+}
+
+/// The per-instance vertex attributes of `Instance`.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct InstanceAttributes {
+    i_transform: [[f32; 3]; 3],
+    i_color: [f32; 4],
+}
+
+implement_vertex!(InstanceAttributes, i_transform, i_color);
+
+impl<'a> From<&'a Instance> for InstanceAttributes {
+    #[inline]
+    fn from(instance: &'a Instance) -> InstanceAttributes {
+        InstanceAttributes {
+            i_transform: instance.matrix(),
+            i_color: instance.color.0,
+        }
+    }
+}
+
+/// Draw `tris` once for each of the `instances` on the CPU, one instance after another.
+pub(crate) fn expand(tris: &[RendTri], instances: &[Instance]) -> Vec<RendTri> {
+    let mut out = Vec::with_capacity(tris.len() * instances.len());
+    for instance in instances {
+        out.extend(tris.iter().map(|t| instance.apply(t)));
+    }
+    out
+}
+
+/// Split `tris` into ranges of consecutive triangles which share a texture and sampler.
+pub(crate) fn runs(tris: &[RendTri]) -> Vec<Range<usize>> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    for i in 1..tris.len() + 1 {
+        if i == tris.len() || !tris[start].same_batch(&tris[i]) {
+            runs.push(start..i);
+            start = i;
+        }
+    }
+    runs
+}
@@ -32,18 +32,43 @@ extern crate image as img;
 extern crate cgmath as cgm;
 #[macro_use]
 extern crate error_chain;
+extern crate xml;
+extern crate rusttype;
+extern crate serde_json;
 
 mod window;
+mod texture;
+mod atlas;
+mod font;
+mod sprite;
+mod animation;
+mod raster;
+mod mesh;
+mod instance;
+mod svg;
 mod shape;
 mod color;
+mod transform;
 mod event;
 mod time;
 
 pub use window::*;
+pub use texture::*;
+pub use atlas::*;
+pub use font::*;
+pub use sprite::*;
+pub use animation::*;
+pub use raster::*;
+pub use mesh::*;
+pub use instance::*;
+pub use svg::*;
 pub use shape::*;
 pub use color::*;
+pub use transform::*;
 pub use event::*;
 pub use time::*;
+/// Re-export of `image::RgbaImage`
+pub use img::RgbaImage;
 /// Re-export of `glium::glutin::ElementState`
 pub use glium::glutin::ElementState as KeyState;
 /// Re-export of `glium::glutin::VirtualKeyCode`
@@ -0,0 +1,166 @@
+use glium;
+use std::fmt;
+use *;
+
+/// A shape whose triangles have been uploaded to the GPU once, so that it can be drawn every
+/// frame without going through its combinators again.
+///
+/// Draw it with `Frame::draw_mesh()`, either as it is or moved and tinted with
+/// `Mesh::instance()`. Meshes suit shapes which do not change, like backgrounds and levels.
+///
+/// Like in `Frame::draw()`, consecutive triangles which share a texture and sampler are kept
+/// in one vertex buffer which is drawn with a single draw call.
+pub struct Mesh {
+    batches: Vec<Batch>,
+    len: usize,
+}
+
+/// Triangles which are drawn together.
+pub(crate) struct Batch {
+    pub(crate) buffer: glium::VertexBuffer<Tri>,
+    pub(crate) texture: Option<Texture>,
+    pub(crate) sampler: Sampler,
+}
+
+impl Mesh {
+    /// Upload the triangles of `shape` and its textures to the GPU of `window`.
+    pub fn new<S: Shape>(window: &Window, shape: S) -> Result<Mesh> {
+        // Each run holds its first triangle, which decides the texture and sampler of the run.
+        let mut runs: Vec<(RendTri, Vec<Tri>)> = Vec::new();
+        let mut len = 0;
+        for rtri in shape {
+            len += 1;
+            let same = runs.last().map_or(false, |run| run.0.same_batch(&rtri));
+            if same {
+                runs.last_mut().unwrap().1.push(rtri.tri);
+            } else {
+                runs.push((rtri.clone(), vec![rtri.tri]));
+            }
+        }
+
+        let mut batches = Vec::with_capacity(runs.len());
+        for (first, tris) in runs {
+            if let Some(ref texture) = first.texture {
+                texture.gpu(&window.display)?;
+            }
+            batches.push(Batch {
+                buffer: glium::VertexBuffer::immutable(&window.display, &tris)?,
+                texture: first.texture,
+                sampler: first.sampler,
+            });
+        }
+        Ok(Mesh {
+            batches: batches,
+            len: len,
+        })
+    }
+
+    /// The number of triangles in the mesh.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the mesh has no triangles.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of draw calls it takes to draw the mesh.
+    #[inline]
+    pub fn draw_calls(&self) -> usize {
+        self.batches.len()
+    }
+
+    /// Get an instance of the mesh which can be moved and tinted before it is drawn.
+    #[inline]
+    pub fn instance(&self) -> MeshInstance<'_> {
+        MeshInstance {
+            mesh: self,
+            instance: Instance::new(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn batches(&self) -> &[Batch] {
+        &self.batches
+    }
+}
+
+impl fmt::Debug for Mesh {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Mesh")
+            .field("len", &self.len)
+            .field("draw_calls", &self.batches.len())
+            .finish()
+    }
+}
+
+/// A `Mesh` together with an `Instance` which describes how to draw it.
+#[derive(Copy, Clone, Debug)]
+pub struct MeshInstance<'a> {
+    mesh: &'a Mesh,
+    instance: Instance,
+}
+
+impl<'a> MeshInstance<'a> {
+    /// Translate the mesh, see `Instance::translate()`.
+    #[inline]
+    pub fn translate<V: Into<cgm::Vector2<f32>>>(self, vector: V) -> MeshInstance<'a> {
+        self.map(|i| i.translate(vector))
+    }
+
+    /// Rotate the mesh around the origin, see `Instance::rotate()`.
+    #[inline]
+    pub fn rotate(self, angle: f32) -> MeshInstance<'a> {
+        self.map(|i| i.rotate(angle))
+    }
+
+    /// Scale the mesh, see `Instance::scale_both()`.
+    #[inline]
+    pub fn scale_both<V: Into<cgm::Vector2<f32>>>(self, scale: V) -> MeshInstance<'a> {
+        self.map(|i| i.scale_both(scale))
+    }
+
+    /// Scale the mesh in both dimensions, see `Instance::scale()`.
+    #[inline]
+    pub fn scale(self, scale: f32) -> MeshInstance<'a> {
+        self.map(|i| i.scale(scale))
+    }
+
+    /// Transform the mesh with an affine `Transform`, see `Instance::transform()`.
+    #[inline]
+    pub fn transform(self, transform: Transform) -> MeshInstance<'a> {
+        self.map(|i| i.transform(transform))
+    }
+
+    /// Multiply all of the colors in the mesh, see `Instance::mul_color()`.
+    #[inline]
+    pub fn mul_color<C: Into<Color>>(self, color: C) -> MeshInstance<'a> {
+        self.map(|i| i.mul_color(color))
+    }
+
+    #[inline]
+    fn map<F: FnOnce(Instance) -> Instance>(mut self, f: F) -> MeshInstance<'a> {
+        self.instance = f(self.instance);
+        self
+    }
+
+    #[inline]
+    pub(crate) fn mesh(&self) -> &'a Mesh {
+        self.mesh
+    }
+
+    #[inline]
+    pub(crate) fn instance(&self) -> &Instance {
+        &self.instance
+    }
+}
+
+impl<'a> From<&'a Mesh> for MeshInstance<'a> {
+    #[inline]
+    fn from(mesh: &'a Mesh) -> MeshInstance<'a> {
+        mesh.instance()
+    }
+}
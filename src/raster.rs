@@ -0,0 +1,295 @@
+use cgm;
+use std::fs::File;
+use std::path;
+use *;
+
+/// A software rasterizer which draws shapes into an RGBA pixel buffer on the CPU.
+///
+/// `Canvas` does not need a `Window`, an OpenGL context or a display server. It reproduces what the
+/// shaders used by `Frame::draw` produce, including triangle colors, texture coordinates, textures
+/// and the radial falloff of untextured triangles, so it can be used for headless rendering and
+/// for testing shapes.
+///
+/// Like a `Window`, the canvas spans from `-1.0` to `1.0` on both axes with `y` pointing up.
+///
+/// # Example
+/// ```rust
+/// use nest::*;
+///
+/// let mut canvas = Canvas::new(64, 64);
+/// canvas.draw(rect([-0.5, -0.5], [0.5, 0.5]));
+/// assert_eq!(canvas.pixel(32, 32), [255, 255, 255, 255]);
+/// assert_eq!(canvas.pixel(0, 0), [0, 0, 0, 255]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Canvas {
+    image: img::RgbaImage,
+    clear_color: Color,
+}
+
+impl Canvas {
+    /// Create a new canvas with a size specified in pixels. It starts out cleared to black.
+    pub fn new(width: u32, height: u32) -> Canvas {
+        let mut canvas = Canvas {
+            image: img::RgbaImage::new(width, height),
+            clear_color: Color::BLACK,
+        };
+        canvas.clear();
+        canvas
+    }
+
+    /// Change the color which the canvas is cleared with by `clear()`.
+    pub fn clear_color<C: Into<Color>>(&mut self, color: C) {
+        self.clear_color = color.into();
+    }
+
+    /// Fill the whole canvas with the clear color.
+    pub fn clear(&mut self) {
+        let color = img::Rgba { data: to_bytes(self.clear_color.0) };
+        for pixel in self.image.pixels_mut() {
+            *pixel = color;
+        }
+    }
+
+    /// Draws the `shape` on top of what is already on the canvas.
+    pub fn draw<S>(&mut self, shape: S) where S: Shape {
+        for rtri in shape {
+            self.draw_tri(&rtri.tri, rtri.texture.as_ref(), &rtri.sampler);
+        }
+    }
+
+    /// Draws the `shape` once for each of the `instances`, like `Frame::draw_instanced()`.
+    pub fn draw_instanced<S>(&mut self, shape: S, instances: &[Instance]) where S: Shape {
+        let tris: Vec<RendTri> = shape.into_iter().collect();
+        self.draw(instance::expand(&tris, instances));
+    }
+
+    /// The width of the canvas in pixels.
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.image.width()
+    }
+
+    /// The height of the canvas in pixels.
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.image.height()
+    }
+
+    /// The RGBA value of the pixel at `(x, y)`, where `(0, 0)` is the top-left corner.
+    #[inline]
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        self.image.get_pixel(x, y).data
+    }
+
+    /// The pixels of the canvas, with the top row first.
+    #[inline]
+    pub fn image(&self) -> &img::RgbaImage {
+        &self.image
+    }
+
+    /// Consume the canvas and get its pixels, with the top row first.
+    #[inline]
+    pub fn into_image(self) -> img::RgbaImage {
+        self.image
+    }
+
+    /// Save the canvas to a PNG file. The file is always PNG encoded, whatever its extension.
+    pub fn save_png<P: AsRef<path::Path>>(&self, path: P) -> Result<()> {
+        let file = File::create(path)?;
+        img::png::PNGEncoder::new(file).encode(
+            &self.image,
+            self.image.width(),
+            self.image.height(),
+            img::ColorType::RGBA(8),
+        )?;
+        Ok(())
+    }
+
+    fn draw_tri(&mut self, tri: &Tri, texture: Option<&Texture>, sampler: &Sampler) {
+        let (width, height) = (self.image.width() as f32, self.image.height() as f32);
+        // Convert from normalized device coordinates to pixel coordinates with y pointing down.
+        let mut p = tri.positions.0;
+        for v in &mut p {
+            *v = [(v[0] + 1.0) / 2.0 * width, (1.0 - v[1]) / 2.0 * height];
+        }
+        let mut t = tri.texcoords.0;
+
+        let mut area = edge(p[0], p[1], p[2]);
+        if !(area.abs() > 0.0) || !area.is_finite() {
+            return;
+        }
+        // Give every triangle the same winding so the edge functions are positive inside of it.
+        if area < 0.0 {
+            p.swap(1, 2);
+            t.swap(1, 2);
+            area = -area;
+        }
+
+        let min_x = clamp_px(p[0][0].min(p[1][0]).min(p[2][0]).floor(), width);
+        let max_x = clamp_px(p[0][0].max(p[1][0]).max(p[2][0]).ceil(), width);
+        let min_y = clamp_px(p[0][1].min(p[1][1]).min(p[2][1]).floor(), height);
+        let max_y = clamp_px(p[0][1].max(p[1][1]).max(p[2][1]).ceil(), height);
+        let top_left = [is_top_left(p[1], p[2]), is_top_left(p[2], p[0]), is_top_left(p[0], p[1])];
+        let image = texture.map(|t| t.image());
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let c = [x as f32 + 0.5, y as f32 + 0.5];
+                let w = [edge(p[1], p[2], c), edge(p[2], p[0], c), edge(p[0], p[1], c)];
+                // Pixels on a shared edge only belong to one of the triangles (the top-left rule).
+                if !(0..3).all(|i| w[i] > 0.0 || (w[i] == 0.0 && top_left[i])) {
+                    continue;
+                }
+                let b = [w[0] / area, w[1] / area, w[2] / area];
+                let texcoord = cgm::Vector2::new(
+                    b[0] * t[0][0] + b[1] * t[1][0] + b[2] * t[2][0],
+                    b[0] * t[0][1] + b[1] * t[1][1] + b[2] * t[2][1],
+                );
+                let src = match image {
+                    Some(ref image) => {
+                        let texel = sample(image, texcoord.x, texcoord.y, sampler);
+                        Color(tri.color).multiply(texel).0
+                    }
+                    None => {
+                        // Matches the falloff in `shader/plain.frag`.
+                        let intensity = 1.0 - (texcoord.x * texcoord.x + texcoord.y * texcoord.y);
+                        Color(tri.color).multiply([intensity; 4]).0
+                    }
+                };
+                self.blend(x, y, src);
+            }
+        }
+    }
+
+    /// Alpha blending as done by `glium::Blend::alpha_blending()`.
+    fn blend(&mut self, x: u32, y: u32, src: [f32; 4]) {
+        let pixel = self.image.get_pixel_mut(x, y);
+        let mut out = [0.0; 4];
+        let alpha = clamp(src[3]);
+        for i in 0..4 {
+            let dst = pixel.data[i] as f32 / 255.0;
+            out[i] = clamp(src[i]) * alpha + dst * (1.0 - alpha);
+        }
+        pixel.data = to_bytes(out);
+    }
+}
+
+/// Render a `shape` into a new image of `width` by `height` pixels without opening a window.
+///
+/// The image is first filled with `clear_color`. See `Canvas` for drawing several shapes.
+///
+/// # Example
+/// ```rust
+/// use nest::*;
+///
+/// let image = render_to_image(rect([-0.5, -0.5], [0.5, 0.5]), 64, 48, Color::BLUE);
+/// assert_eq!(image.dimensions(), (64, 48));
+/// ```
+pub fn render_to_image<S, C>(shape: S, width: u32, height: u32, clear_color: C) -> img::RgbaImage
+where
+    S: Shape,
+    C: Into<Color>,
+{
+    let mut canvas = Canvas::new(width, height);
+    canvas.clear_color(clear_color);
+    canvas.clear();
+    canvas.draw(shape);
+    canvas.into_image()
+}
+
+/// Render a `shape` like `render_to_image()` and save it to the PNG file at `path`. Like
+/// `Canvas::save_png()`, the file is always PNG encoded, whatever its extension.
+///
+/// # Example
+/// ```rust,no_run
+/// use nest::*;
+///
+/// let petal = image_w(Texture::open("examples/petal.png").unwrap(), 0.4);
+/// render_to_png(petal, 256, 256, Color::BLACK, "petal.png").unwrap();
+/// ```
+pub fn render_to_png<S, C, P>(shape: S, width: u32, height: u32, clear_color: C, path: P) -> Result<()>
+where
+    S: Shape,
+    C: Into<Color>,
+    P: AsRef<path::Path>,
+{
+    let image = img::ImageRgba8(render_to_image(shape, width, height, clear_color));
+    image.save(&mut File::create(path)?, img::PNG)?;
+    Ok(())
+}
+
+/// Twice the signed area of the triangle `abc`.
+#[inline]
+fn edge(a: [f32; 2], b: [f32; 2], c: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+#[inline]
+fn is_top_left(a: [f32; 2], b: [f32; 2]) -> bool {
+    let d = [b[0] - a[0], b[1] - a[1]];
+    (d[1] == 0.0 && d[0] > 0.0) || d[1] < 0.0
+}
+
+#[inline]
+fn clamp_px(v: f32, max: f32) -> u32 {
+    v.max(0.0).min(max) as u32
+}
+
+#[inline]
+fn clamp(v: f32) -> f32 {
+    v.max(0.0).min(1.0)
+}
+
+#[inline]
+fn to_bytes(color: [f32; 4]) -> [u8; 4] {
+    [
+        (clamp(color[0]) * 255.0).round() as u8,
+        (clamp(color[1]) * 255.0).round() as u8,
+        (clamp(color[2]) * 255.0).round() as u8,
+        (clamp(color[3]) * 255.0).round() as u8,
+    ]
+}
+
+/// Sample an image like the GPU does with `sampler`. Neither mipmaps nor minification are
+/// supported, so the magnification filter is always used.
+fn sample(image: &img::RgbaImage, u: f32, v: f32, sampler: &Sampler) -> [f32; 4] {
+    let (width, height) = (image.width() as i64, image.height() as i64);
+    if width == 0 || height == 0 {
+        return [0.0; 4];
+    }
+    let texel = |x: i64, y: i64| {
+        let (x, y) = (wrap(x, width, sampler.wrap), wrap(y, height, sampler.wrap));
+        image.get_pixel(x as u32, y as u32).data
+    };
+    if sampler.magnify == Filter::Nearest {
+        let t = texel((u * width as f32).floor() as i64, (v * height as f32).floor() as i64);
+        return [t[0] as f32 / 255.0, t[1] as f32 / 255.0, t[2] as f32 / 255.0, t[3] as f32 / 255.0];
+    }
+    let x = u * width as f32 - 0.5;
+    let y = v * height as f32 - 0.5;
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let (x0, y0) = (x0 as i64, y0 as i64);
+    let (a, b, c, d) = (texel(x0, y0), texel(x0 + 1, y0), texel(x0, y0 + 1), texel(x0 + 1, y0 + 1));
+    let mut out = [0.0; 4];
+    for i in 0..4 {
+        let top = a[i] as f32 * (1.0 - fx) + b[i] as f32 * fx;
+        let bottom = c[i] as f32 * (1.0 - fx) + d[i] as f32 * fx;
+        out[i] = (top * (1.0 - fy) + bottom * fy) / 255.0;
+    }
+    out
+}
+
+/// Wrap the texel coordinate `i` into an image which is `size` texels large.
+#[inline]
+fn wrap(i: i64, size: i64, wrap: Wrap) -> i64 {
+    match wrap {
+        Wrap::Clamp => i.max(0).min(size - 1),
+        Wrap::Repeat => ((i % size) + size) % size,
+        Wrap::Mirror => {
+            let i = ((i % (2 * size)) + 2 * size) % (2 * size);
+            if i < size { i } else { 2 * size - 1 - i }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+#version 150
+
+in mat3x2 positions;
+in mat3x2 texcoords;
+in vec4 color;
+
+in mat3 i_transform;
+in vec4 i_color;
+
+out mat3x2 v_positions;
+out mat3x2 v_texcoords;
+out vec4 v_color;
+
+void main() {
+    v_positions = mat3x2(
+        (i_transform * vec3(positions[0], 1.0)).xy,
+        (i_transform * vec3(positions[1], 1.0)).xy,
+        (i_transform * vec3(positions[2], 1.0)).xy
+    );
+    v_texcoords = texcoords;
+    v_color = color * i_color;
+}
@@ -0,0 +1,68 @@
+use *;
+
+/// A named point on the bounding box of a shape, used to pivot it with
+/// `Shape::rotate_about_anchor()` and `Shape::scale_about_anchor()`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Anchor {
+    /// The top left corner.
+    TopLeft,
+    /// The middle of the top edge.
+    TopCenter,
+    /// The top right corner.
+    TopRight,
+    /// The middle of the left edge.
+    CenterLeft,
+    /// The center of the bounding box.
+    Center,
+    /// The middle of the right edge.
+    CenterRight,
+    /// The bottom left corner.
+    BottomLeft,
+    /// The middle of the bottom edge.
+    BottomCenter,
+    /// The bottom right corner.
+    BottomRight,
+}
+
+impl Anchor {
+    /// The position of the anchor on the rectangle `bounds`, where `y` points up.
+    ///
+    /// # Example
+    /// ```rust
+    /// use nest::*;
+    ///
+    /// let bounds = Rect([-1.0, 0.0], [1.0, 2.0]);
+    /// assert_eq!(Anchor::TopLeft.point(bounds), [-1.0, 2.0].into());
+    /// assert_eq!(Anchor::BottomCenter.point(bounds), [0.0, 0.0].into());
+    /// ```
+    pub fn point(self, bounds: Rect) -> cgm::Point2<f32> {
+        let (min, max) = (bounds.0, bounds.1);
+        let (x, y) = match self {
+            Anchor::TopLeft => (0.0, 1.0),
+            Anchor::TopCenter => (0.5, 1.0),
+            Anchor::TopRight => (1.0, 1.0),
+            Anchor::CenterLeft => (0.0, 0.5),
+            Anchor::Center => (0.5, 0.5),
+            Anchor::CenterRight => (1.0, 0.5),
+            Anchor::BottomLeft => (0.0, 0.0),
+            Anchor::BottomCenter => (0.5, 0.0),
+            Anchor::BottomRight => (1.0, 0.0),
+        };
+        cgm::Point2::new(min[0] + (max[0] - min[0]) * x, min[1] + (max[1] - min[1]) * y)
+    }
+}
+
+/// The smallest rectangle which contains all of the triangles of `shape`, with the smallest
+/// coordinates first, or `None` if it has no triangles.
+pub(crate) fn bounds<S: Shape>(shape: S) -> Option<Rect> {
+    let mut bounds: Option<Rect> = None;
+    for rtri in shape {
+        for p in &rtri.tri.positions.0 {
+            bounds = Some(match bounds {
+                Some(b) => Rect([b.0[0].min(p[0]), b.0[1].min(p[1])], [b.1[0].max(p[0]), b.1[1].max(p[1])]),
+                None => Rect(*p, *p),
+            });
+        }
+    }
+    bounds
+}
@@ -0,0 +1,200 @@
+use ::*;
+use std::f32::consts::PI;
+
+/// The default distance the tessellated outline may stray from the true curve.
+/// This is about half a pixel on a 480 pixel high window.
+pub(crate) const DEFAULT_TOLERANCE: f32 = 0.002;
+/// The most segments a curve is split into, however large it is or however many are asked for.
+const MAX_SEGMENTS: usize = 4096;
+
+/// How finely a curved shape is split into triangles.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Detail {
+    /// Use exactly this many segments along the curve.
+    Segments(usize),
+    /// Use as few segments as possible while keeping the outline within this distance of the
+    /// true curve.
+    Tolerance(f32),
+}
+
+impl Detail {
+    /// The number of segments needed to cover `angle` radians of an ellipse with the largest
+    /// radius `radius`. A full turn has at least 3 segments and no curve has more than 4096.
+    pub(crate) fn segments(self, radius: f32, angle: f32) -> usize {
+        let angle = angle.abs();
+        let min = if angle >= 2.0 * PI { 3 } else { 1 };
+        match self {
+            Detail::Segments(n) => n.max(min).min(MAX_SEGMENTS),
+            Detail::Tolerance(tolerance) => {
+                if !(tolerance > 0.0) || !(radius > tolerance) {
+                    return min;
+                }
+                // Each segment is a chord whose midpoint may be `tolerance` away from the curve.
+                let step = 2.0 * (1.0 - tolerance / radius).acos();
+                // For huge radii the cosine rounds to 1 and the step to 0.
+                if !(step > 0.0) || !step.is_finite() {
+                    return MAX_SEGMENTS;
+                }
+                let n = (angle / step).ceil();
+                if n < MAX_SEGMENTS as f32 {
+                    (n as usize).max(min)
+                } else {
+                    MAX_SEGMENTS
+                }
+            }
+        }
+    }
+}
+
+/// An ellipse, or a slice or ring of one.
+///
+/// Created by `circle()`, `ellipse()`, `pie()` and `arc()`.
+#[derive(Copy, Clone, Debug)]
+pub struct Ellipse {
+    center: [f32; 2],
+    radii: [f32; 2],
+    inner_radii: [f32; 2],
+    start: f32,
+    end: f32,
+    detail: Detail,
+}
+
+impl Ellipse {
+    /// Split the curve into exactly `segments` segments. Full ellipses use at least 3, and no
+    /// curve uses more than 4096.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// // A hexagon.
+    /// app.draw(circle([0.0, 0.0], 0.5).segments(6));
+    /// ```
+    #[inline]
+    pub fn segments(mut self, segments: usize) -> Ellipse {
+        self.detail = Detail::Segments(segments);
+        self
+    }
+
+    /// Split the curve into as few segments as possible while keeping the outline within
+    /// `tolerance` of the true curve.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// app.draw(circle([0.0, 0.0], 0.5).tolerance(0.01));
+    /// ```
+    #[inline]
+    pub fn tolerance(mut self, tolerance: f32) -> Ellipse {
+        self.detail = Detail::Tolerance(tolerance);
+        self
+    }
+
+    #[inline]
+    fn point(&self, radii: [f32; 2], angle: f32) -> [f32; 2] {
+        [
+            self.center[0] + radii[0] * angle.cos(),
+            self.center[1] + radii[1] * angle.sin(),
+        ]
+    }
+}
+
+impl IntoIterator for Ellipse {
+    type IntoIter = EllipseIter;
+    type Item = RendTri;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let radius = self.radii[0].abs().max(self.radii[1].abs());
+        EllipseIter {
+            segments: self.detail.segments(radius, self.end - self.start),
+            ellipse: self,
+            segment: 0,
+            second: false,
+        }
+    }
+}
+
+/// Iterator which is produced by `Ellipse`
+#[derive(Clone, Debug)]
+pub struct EllipseIter {
+    ellipse: Ellipse,
+    segments: usize,
+    segment: usize,
+    second: bool,
+}
+
+impl Iterator for EllipseIter {
+    type Item = RendTri;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.segment >= self.segments {
+            return None;
+        }
+        let e = &self.ellipse;
+        let span = e.end - e.start;
+        let a0 = e.start + span * self.segment as f32 / self.segments as f32;
+        let a1 = e.start + span * (self.segment + 1) as f32 / self.segments as f32;
+
+        let tri = if e.inner_radii == [0.0, 0.0] {
+            // A filled slice is a fan around the center.
+            self.segment += 1;
+            Tri::new_pos([e.center, e.point(e.radii, a0), e.point(e.radii, a1)])
+        } else if !self.second {
+            self.second = true;
+            Tri::new_pos([e.point(e.inner_radii, a0), e.point(e.radii, a0), e.point(e.radii, a1)])
+        } else {
+            self.second = false;
+            self.segment += 1;
+            Tri::new_pos([e.point(e.inner_radii, a0), e.point(e.radii, a1), e.point(e.inner_radii, a1)])
+        };
+        Some(tri.into())
+    }
+}
+
+/// Takes a center point and a radius and creates a circle.
+#[inline]
+pub fn circle<C: Into<cgm::Point2<f32>>>(center: C, radius: f32) -> Ellipse {
+    ellipse(center, [radius, radius])
+}
+
+/// Takes a center point and the radii along the x and y axes and creates an ellipse.
+#[inline]
+pub fn ellipse<C: Into<cgm::Point2<f32>>, R: Into<cgm::Vector2<f32>>>(center: C, radii: R) -> Ellipse {
+    let radii = radii.into();
+    Ellipse {
+        center: center.into().into(),
+        radii: [radii.x, radii.y],
+        inner_radii: [0.0, 0.0],
+        start: 0.0,
+        end: 2.0 * PI,
+        detail: Detail::Tolerance(DEFAULT_TOLERANCE),
+    }
+}
+
+/// Takes a center point, a radius and the angles in radians to start and end at and creates
+/// a filled slice of a circle. Angles are measured counter-clockwise from the positive x axis.
+#[inline]
+pub fn pie<C: Into<cgm::Point2<f32>>>(center: C, radius: f32, start: f32, end: f32) -> Ellipse {
+    Ellipse {
+        start: start,
+        end: end,
+        ..circle(center, radius)
+    }
+}
+
+/// Takes a center point, a radius, the angles in radians to start and end at, and a thickness
+/// and creates the outline of part of a circle. The outline is centered on `radius`.
+/// Angles are measured counter-clockwise from the positive x axis.
+#[inline]
+pub fn arc<C: Into<cgm::Point2<f32>>>(center: C, radius: f32, start: f32, end: f32, thickness: f32) -> Ellipse {
+    let outer = radius + thickness / 2.0;
+    let inner = (radius - thickness / 2.0).max(0.0);
+    Ellipse {
+        inner_radii: [inner, inner],
+        start: start,
+        end: end,
+        ..circle(center, outer)
+    }
+}
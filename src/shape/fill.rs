@@ -0,0 +1,125 @@
+/// Decides which areas enclosed by a path are inside of it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FillRule {
+    /// A point is inside if a ray from it crosses the outlines an odd number of times.
+    EvenOdd,
+    /// A point is inside unless the outlines wind around it clockwise as often as
+    /// counter-clockwise.
+    NonZero,
+}
+
+impl FillRule {
+    #[inline]
+    fn inside(self, winding: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0,
+        }
+    }
+}
+
+/// An edge of an outline which is not horizontal, with `low[1] < high[1]`.
+#[derive(Copy, Clone, Debug)]
+struct Edge {
+    low: [f32; 2],
+    high: [f32; 2],
+    winding: i32,
+}
+
+impl Edge {
+    #[inline]
+    fn x_at(&self, y: f32) -> f32 {
+        let t = (y - self.low[1]) / (self.high[1] - self.low[1]);
+        self.low[0] + (self.high[0] - self.low[0]) * t
+    }
+}
+
+/// Triangulate the area inside of any number of closed outlines, which may intersect themselves
+/// and each other.
+///
+/// The plane is cut into horizontal bands at every point and every intersection of the outlines.
+/// No two edges cross inside of a band, so the inside of each band is made of trapezoids which are
+/// found by sorting the edges along the band and following the winding number.
+pub(crate) fn fill(outlines: &[Vec<[f32; 2]>], rule: FillRule) -> Vec<[[f32; 2]; 3]> {
+    let mut edges = Vec::new();
+    for outline in outlines {
+        for i in 0..outline.len() {
+            let (a, b) = (outline[i], outline[(i + 1) % outline.len()]);
+            if a[1] < b[1] {
+                edges.push(Edge { low: a, high: b, winding: 1 });
+            } else if a[1] > b[1] {
+                edges.push(Edge { low: b, high: a, winding: -1 });
+            }
+        }
+    }
+
+    let mut ys: Vec<f32> = edges.iter().flat_map(|e| vec![e.low[1], e.high[1]]).collect();
+    for i in 0..edges.len() {
+        for j in i + 1..edges.len() {
+            if let Some(y) = intersection_y(&edges[i], &edges[j]) {
+                ys.push(y);
+            }
+        }
+    }
+    ys.retain(|y| y.is_finite());
+    ys.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    ys.dedup();
+
+    let mut tris = Vec::new();
+    let mut active: Vec<(f32, f32, f32, i32)> = Vec::new();
+    for band in ys.windows(2) {
+        let (y0, y1) = (band[0], band[1]);
+        let mid = (y0 + y1) / 2.0;
+        active.clear();
+        active.extend(
+            edges
+                .iter()
+                .filter(|e| e.low[1] <= y0 && e.high[1] >= y1)
+                .map(|e| (e.x_at(mid), e.x_at(y0), e.x_at(y1), e.winding)),
+        );
+        active.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let mut winding = 0;
+        let mut left = None;
+        for &(_, x0, x1, w) in &active {
+            let was_inside = rule.inside(winding);
+            winding += w;
+            match (was_inside, rule.inside(winding)) {
+                (false, true) => left = Some((x0, x1)),
+                (true, false) => {
+                    if let Some((l0, l1)) = left.take() {
+                        trapezoid([l0, y0], [x0, y0], [x1, y1], [l1, y1], &mut tris);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    tris
+}
+
+/// The height at which two edges cross, if they cross away from their end points.
+fn intersection_y(a: &Edge, b: &Edge) -> Option<f32> {
+    let low = a.low[1].max(b.low[1]);
+    let high = a.high[1].min(b.high[1]);
+    if low >= high {
+        return None;
+    }
+    // The horizontal distance between the edges changes linearly with the height.
+    let d0 = a.x_at(low) - b.x_at(low);
+    let d1 = a.x_at(high) - b.x_at(high);
+    if (d0 < 0.0 && d1 > 0.0) || (d0 > 0.0 && d1 < 0.0) {
+        Some(low + (high - low) * d0 / (d0 - d1))
+    } else {
+        None
+    }
+}
+
+fn trapezoid(a: [f32; 2], b: [f32; 2], c: [f32; 2], d: [f32; 2], tris: &mut Vec<[[f32; 2]; 3]>) {
+    if a != b {
+        tris.push([a, b, c]);
+    }
+    if c != d {
+        tris.push([a, c, d]);
+    }
+}
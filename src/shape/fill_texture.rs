@@ -0,0 +1,66 @@
+use {cgm, RendTri, Shape, Texture};
+
+/// `FillTexture` represents a shape which has been filled with a texture that repeats across
+/// space.
+#[derive(Clone, Debug)]
+pub struct FillTexture<S> {
+    shape: S,
+    texture: Texture,
+    scale: cgm::Vector2<f32>,
+    offset: cgm::Vector2<f32>,
+}
+
+impl<S> FillTexture<S> {
+    pub(crate) fn new(shape: S, texture: Texture, scale: cgm::Vector2<f32>, offset: cgm::Vector2<f32>) -> Self {
+        FillTexture {
+            shape: shape,
+            texture: texture,
+            scale: scale,
+            offset: offset,
+        }
+    }
+}
+
+impl<S> IntoIterator for FillTexture<S>
+where
+    S: Shape,
+{
+    type Item = RendTri;
+    type IntoIter = FillTextureIter<S::IntoIter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        FillTextureIter {
+            iter: self.shape.into_iter(),
+            texture: self.texture,
+            scale: self.scale,
+            offset: self.offset,
+        }
+    }
+}
+
+/// Iterator which is produced by `FillTexture`
+#[derive(Clone, Debug)]
+pub struct FillTextureIter<I> {
+    iter: I,
+    texture: Texture,
+    scale: cgm::Vector2<f32>,
+    offset: cgm::Vector2<f32>,
+}
+
+impl<I> Iterator for FillTextureIter<I>
+where
+    I: Iterator<Item = RendTri>,
+{
+    type Item = RendTri;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (scale, offset) = (self.scale, self.offset);
+        self.iter.next().map(|mut t| {
+            // The texture is upright, so its rows go down while y goes up.
+            t.tri.texcoords = t.tri.positions.map(|p| {
+                cgm::Point2::new((p.x - offset.x) / scale.x, (offset.y - p.y) / scale.y)
+            });
+            t.map_texture(self.texture.clone())
+        })
+    }
+}
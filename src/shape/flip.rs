@@ -0,0 +1,131 @@
+use {cgm, RendTri, Shape};
+use std::collections::HashSet;
+use std::vec;
+
+/// `Flip` represents a shape whose texture has been mirrored. Each piece of the shape is mirrored
+/// around the middle of its own texture coordinates.
+#[derive(Copy, Clone, Debug)]
+pub struct Flip<S> {
+    shape: S,
+    x: bool,
+    y: bool,
+}
+
+impl<S> Flip<S> {
+    pub(crate) fn new(shape: S, x: bool, y: bool) -> Self {
+        Flip {
+            shape: shape,
+            x: x,
+            y: y,
+        }
+    }
+}
+
+impl<S> IntoIterator for Flip<S>
+where
+    S: Shape,
+{
+    type Item = RendTri;
+    type IntoIter = FlipIter<S::IntoIter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        FlipIter {
+            iter: self.shape.into_iter(),
+            next: None,
+            piece: Vec::new().into_iter(),
+            x: self.x,
+            y: self.y,
+        }
+    }
+}
+
+/// Iterator which is produced by `Flip`
+#[derive(Clone, Debug)]
+pub struct FlipIter<I> {
+    iter: I,
+    /// The first triangle of the next piece, if it has been read already.
+    next: Option<RendTri>,
+    /// The mirrored triangles of the current piece.
+    piece: vec::IntoIter<RendTri>,
+    x: bool,
+    y: bool,
+}
+
+impl<I> FlipIter<I>
+where
+    I: Iterator<Item = RendTri>,
+{
+    /// Read the next piece, which is a run of triangles with the same texture where every triangle
+    /// shares a corner, with the same position and texture coordinates, with one before it. An
+    /// image is a single piece, while every glyph of a text or frame of a sprite sheet is its own.
+    fn read_piece(&mut self) -> Vec<RendTri> {
+        let first = match self.next.take().or_else(|| self.iter.next()) {
+            Some(first) => first,
+            None => return Vec::new(),
+        };
+        let mut corners = HashSet::new();
+        add_corners(&first, &mut corners);
+        let mut piece = vec![first];
+        while let Some(t) = self.iter.next() {
+            let connected = piece[0].same_batch(&t) && (0..3).any(|i| corners.contains(&corner(&t, i)));
+            if !connected {
+                self.next = Some(t);
+                break;
+            }
+            add_corners(&t, &mut corners);
+            piece.push(t);
+        }
+        piece
+    }
+}
+
+impl<I> Iterator for FlipIter<I>
+where
+    I: Iterator<Item = RendTri>,
+{
+    type Item = RendTri;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(t) = self.piece.next() {
+            return Some(t);
+        }
+        let piece = self.read_piece();
+        let mut min = [f32::INFINITY; 2];
+        let mut max = [f32::NEG_INFINITY; 2];
+        for t in &piece {
+            for p in &t.tri.texcoords.0 {
+                for i in 0..2 {
+                    min[i] = min[i].min(p[i]);
+                    max[i] = max[i].max(p[i]);
+                }
+            }
+        }
+        // Mirroring around the middle of the bounds keeps regions of a texture in place.
+        let m = cgm::Point2::new((min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0);
+        let (x, y) = (self.x, self.y);
+        let piece: Vec<RendTri> = piece
+            .into_iter()
+            .map(|t| {
+                t.map_tex(|p| {
+                    cgm::Point2::new(if x { 2.0 * m.x - p.x } else { p.x }, if y { 2.0 * m.y - p.y } else { p.y })
+                })
+            })
+            .collect();
+        self.piece = piece.into_iter();
+        self.piece.next()
+    }
+}
+
+/// The position and texture coordinates of corner `i` of a triangle, as bits which can be hashed.
+#[inline]
+fn corner(t: &RendTri, i: usize) -> [u32; 4] {
+    let (p, uv) = (t.tri.positions.0[i], t.tri.texcoords.0[i]);
+    [p[0].to_bits(), p[1].to_bits(), uv[0].to_bits(), uv[1].to_bits()]
+}
+
+#[inline]
+fn add_corners(t: &RendTri, corners: &mut HashSet<[u32; 4]>) {
+    for i in 0..3 {
+        corners.insert(corner(t, i));
+    }
+}
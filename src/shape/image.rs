@@ -1,13 +1,27 @@
 use *;
-use glium::texture::Texture2d;
 use std::iter::{Chain, Once, once};
-use std::rc::Rc;
 
 /// Texture rectangle.
 #[derive(Clone, Debug)]
 pub struct Image {
     rect: Rect,
-    texture: Rc<Texture2d>,
+    /// The texture coordinates at the two corners of `rect`.
+    uv: Rect,
+    texture: Texture,
+}
+
+impl Image {
+    /// Create an image which stretches `region` over `rect`, with the top of the region at the
+    /// top of `rect` if `rect[0]` is the bottom left corner.
+    #[inline]
+    fn new(rect: Rect, region: TextureRegion) -> Image {
+        let (min, max) = region.uv();
+        Image {
+            rect: rect,
+            uv: Rect([min[0], max[1]], [max[0], min[1]]),
+            texture: region.texture().clone(),
+        }
+    }
 }
 
 impl IntoIterator for Image {
@@ -23,9 +37,9 @@ impl IntoIterator for Image {
                 [self.rect.0[0], self.rect.1[1]],
             ],
             [
-                [1.0, 1.0],
-                [0.0, 1.0],
-                [1.0, 0.0],
+                [self.uv.0[0], self.uv.0[1]],
+                [self.uv.1[0], self.uv.0[1]],
+                [self.uv.0[0], self.uv.1[1]],
             ],
             Color::WHITE,
         )).map_texture(self.texture.clone())), once(RendTri::from(Tri::new(
@@ -35,9 +49,9 @@ impl IntoIterator for Image {
                 [self.rect.1[0], self.rect.0[1]],
             ],
             [
-                [0.0, 0.0],
-                [1.0, 0.0],
-                [0.0, 1.0],
+                [self.uv.1[0], self.uv.1[1]],
+                [self.uv.0[0], self.uv.1[1]],
+                [self.uv.1[0], self.uv.0[1]],
             ],
             Color::WHITE,
         )).map_texture(self.texture)))
@@ -45,36 +59,49 @@ impl IntoIterator for Image {
 }
 
 /// Takes two points and a texture and draws the texture on the rectangle specified by the two points.
+///
+/// If `first` is the bottom left corner and `second` the top right one, the texture is drawn
+/// upright, with its first pixel in the top left corner.
 #[inline]
 pub fn image<A, B, T>(first: A, second: B, texture: T) -> Image
-    where A: Into<cgm::Point2<f32>>, B: Into<cgm::Point2<f32>>, T: Into<Rc<Texture2d>>
+    where A: Into<cgm::Point2<f32>>, B: Into<cgm::Point2<f32>>, T: Into<Texture>
 {
-    Image {
-        rect: Rect(first.into().into(), second.into().into()),
-        texture: texture.into(),
-    }
+    image_region(first, second, TextureRegion::from(texture.into()))
+}
+
+/// Takes two points and a region of a texture and draws the region on the rectangle specified by
+/// the two points.
+///
+/// ## Example
+/// ```rust,no_run
+/// use nest::*;
+/// let mut app = Window::new("Example", 640, 480).unwrap();
+/// let sheet = app.load_texture("sheet.png").unwrap();
+/// // The 32x32 pixel sprite in the top left corner of the sheet.
+/// let sprite = TextureRegion::from_pixels(sheet, [0, 0], [32, 32]);
+/// app.draw(image_region([-0.5, -0.5], [0.5, 0.5], sprite));
+/// ```
+#[inline]
+pub fn image_region<A, B, R>(first: A, second: B, region: R) -> Image
+    where A: Into<cgm::Point2<f32>>, B: Into<cgm::Point2<f32>>, R: Into<TextureRegion>
+{
+    Image::new(Rect(first.into().into(), second.into().into()), region.into())
 }
 
 /// Takes a width and a texture and automatically adjusts the height to be proportional for the texture.
 /// The resulting image rectangle is centered at the origin.
 #[inline]
-pub fn image_w<T: Into<Rc<Texture2d>>>(texture: T, width: f32) -> Image {
-    let tex = texture.into();
-    let height = tex.height() as f32 / tex.width() as f32 * width;
-    Image {
-        rect: Rect([-width / 2.0, -height / 2.0], [width / 2.0, height / 2.0]),
-        texture: tex,
-    }
+pub fn image_w<T: Into<TextureRegion>>(texture: T, width: f32) -> Image {
+    let region = texture.into();
+    let height = region.height() / region.width() * width;
+    Image::new(Rect([-width / 2.0, -height / 2.0], [width / 2.0, height / 2.0]), region)
 }
 
 /// Takes a height and a texture and automatically adjusts the width to be proportional for the texture.
 /// The resulting image rectangle is centered at the origin.
 #[inline]
-pub fn image_h<T: Into<Rc<Texture2d>>>(texture: T, height: f32) -> Image {
-    let tex = texture.into();
-    let width = tex.width() as f32 / tex.height() as f32 * height;
-    Image {
-        rect: Rect([-width / 2.0, -height / 2.0], [width / 2.0, height / 2.0]),
-        texture: tex,
-    }
-}
\ No newline at end of file
+pub fn image_h<T: Into<TextureRegion>>(texture: T, height: f32) -> Image {
+    let region = texture.into();
+    let width = region.width() / region.height() * height;
+    Image::new(Rect([-width / 2.0, -height / 2.0], [width / 2.0, height / 2.0]), region)
+}
@@ -2,29 +2,56 @@ use glium;
 use cgm;
 
 use Color;
-use std::rc::Rc;
-use glium::texture::Texture2d;
+use Sampler;
+use Texture;
+use Transform;
 
-mod translate;
-mod rotate;
-mod scale;
+mod transform;
 mod combine;
 mod image;
 mod rect;
+mod ellipse;
+mod polygon;
+mod stroke;
+mod polyline;
+mod fill;
+mod path;
+mod text;
+mod nine_slice;
+mod anchor;
 mod recolor;
 mod mulcolor;
+mod resample;
+mod fill_texture;
+mod translate_uv;
+mod rotate_uv;
+mod scale_uv;
+mod flip;
 
 // Combinator helper structs
-use self::translate::*;
-use self::rotate::*;
-use self::scale::*;
+use self::transform::*;
 use self::combine::*;
 use self::recolor::*;
 use self::mulcolor::*;
+use self::resample::*;
+use self::fill_texture::*;
+use self::translate_uv::*;
+use self::rotate_uv::*;
+use self::scale_uv::*;
+use self::flip::*;
 
 // User types
 pub use self::image::*;
 pub use self::rect::*;
+pub use self::ellipse::*;
+pub use self::polygon::*;
+pub use self::stroke::*;
+pub use self::polyline::*;
+pub use self::fill::*;
+pub use self::path::*;
+pub use self::text::*;
+pub use self::nine_slice::*;
+pub use self::anchor::*;
 
 /// Trait for structs to be drawn with `Frame::draw`
 pub trait Shape: IntoIterator<Item = RendTri> {
@@ -43,8 +70,8 @@ pub trait Shape: IntoIterator<Item = RendTri> {
     /// app.draw(Rect([-0.5, -0.5], [0.5, 0.5]).translate([0.1, 0.1]));
     /// ```
     #[inline]
-    fn translate<V: Into<cgm::Vector2<f32>>>(&self, vector: V) -> Translate<Self> where Self: Clone {
-        Translate::new(self.clone(), vector.into())
+    fn translate<V: Into<cgm::Vector2<f32>>>(&self, vector: V) -> Transformed<Self> where Self: Clone {
+        Transformed::new(self.clone(), Transform::translation(vector))
     }
 
     /// Rotate a shape using an angle in radians.
@@ -57,8 +84,8 @@ pub trait Shape: IntoIterator<Item = RendTri> {
     /// app.draw(Rect([-0.5, -0.5], [0.5, 0.5]).rotate(PI));
     /// ```
     #[inline]
-    fn rotate(&self, angle: f32) -> Rotate<Self> where Self: Clone {
-        Rotate::new(self.clone(), angle)
+    fn rotate(&self, angle: f32) -> Transformed<Self> where Self: Clone {
+        Transformed::new(self.clone(), Transform::rotation(angle))
     }
 
     /// Scale a shape using a `vector` which represents the magnitude to scale
@@ -71,8 +98,8 @@ pub trait Shape: IntoIterator<Item = RendTri> {
     /// app.draw(Rect([-0.5, -0.5], [0.5, 0.5]).scale_both([0.1, 0.1]));
     /// ```
     #[inline]
-    fn scale_both<V: Into<cgm::Vector2<f32>>>(&self, scale: V) -> Scale<Self> where Self: Clone {
-        Scale::new(self.clone(), scale.into())
+    fn scale_both<V: Into<cgm::Vector2<f32>>>(&self, scale: V) -> Transformed<Self> where Self: Clone {
+        Transformed::new(self.clone(), Transform::scaling(scale))
     }
 
     /// Scale a shape in both dimensions using a `f32` which represents the
@@ -85,8 +112,8 @@ pub trait Shape: IntoIterator<Item = RendTri> {
     /// app.draw(Rect([-0.5, -0.5], [0.5, 0.5]).scale(0.1));
     /// ```
     #[inline]
-    fn scale(&self, scale: f32) -> Scale<Self> where Self: Clone {
-        Scale::new(self.clone(), cgm::Vector2::new(scale, scale))
+    fn scale(&self, scale: f32) -> Transformed<Self> where Self: Clone {
+        Transformed::new(self.clone(), Transform::scaling([scale, scale]))
     }
 
     /// Scale a shape in the x dimension using a `f32` which represents the
@@ -99,8 +126,8 @@ pub trait Shape: IntoIterator<Item = RendTri> {
     /// app.draw(Rect([-0.5, -0.5], [0.5, 0.5]).scale_x(0.1));
     /// ```
     #[inline]
-    fn scale_x(&self, scale_x: f32) -> Scale<Self> where Self: Clone {
-        Scale::new(self.clone(), cgm::Vector2::new(scale_x, 1.0))
+    fn scale_x(&self, scale_x: f32) -> Transformed<Self> where Self: Clone {
+        Transformed::new(self.clone(), Transform::scaling([scale_x, 1.0]))
     }
 
     /// Scale a shape in the y dimension using a `f32` which represents the
@@ -113,8 +140,182 @@ pub trait Shape: IntoIterator<Item = RendTri> {
     /// app.draw(Rect([-0.5, -0.5], [0.5, 0.5]).scale_y(0.1));
     /// ```
     #[inline]
-    fn scale_y(&self, scale_y: f32) -> Scale<Self> where Self: Clone {
-        Scale::new(self.clone(), cgm::Vector2::new(1.0, scale_y))
+    fn scale_y(&self, scale_y: f32) -> Transformed<Self> where Self: Clone {
+        Transformed::new(self.clone(), Transform::scaling([1.0, scale_y]))
+    }
+
+    /// Rotate a shape around `pivot` using an angle in radians.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// use std::f32::consts::PI;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// // Spin a square around its own center.
+    /// app.draw(Rect([0.2, 0.2], [0.6, 0.6]).rotate_about([0.4, 0.4], PI / 4.0));
+    /// ```
+    #[inline]
+    fn rotate_about<P: Into<cgm::Point2<f32>>>(&self, pivot: P, angle: f32) -> Transformed<Self> where Self: Clone {
+        Transformed::new(self.clone(), Transform::IDENTITY.rotate_about(pivot, angle))
+    }
+
+    /// Scale a shape in both dimensions away from `pivot`, which stays where it is.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// // Grow a bar upwards from its bottom edge.
+    /// app.draw(Rect([-0.1, -0.5], [0.1, 0.0]).scale_about([0.0, -0.5], 2.0));
+    /// ```
+    #[inline]
+    fn scale_about<P: Into<cgm::Point2<f32>>>(&self, pivot: P, scale: f32) -> Transformed<Self> where Self: Clone {
+        Transformed::new(self.clone(), Transform::IDENTITY.scale_about(pivot, scale))
+    }
+
+    /// Rotate a shape using an angle in radians around a point on its bounding box, such as
+    /// `Anchor::Center`. The bounding box is found by going through all of the triangles of the
+    /// shape once.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// let petal = image_w(app.load_texture("examples/petal.png").unwrap(), 0.4).translate([0.3, 0.0]);
+    /// app.draw(petal.rotate_about_anchor(Anchor::Center, 0.5));
+    /// ```
+    #[inline]
+    fn rotate_about_anchor(&self, anchor: Anchor, angle: f32) -> Transformed<Self> where Self: Clone {
+        self.rotate_about(anchor_point(self, anchor), angle)
+    }
+
+    /// Scale a shape in both dimensions away from a point on its bounding box, such as
+    /// `Anchor::BottomCenter`. See `rotate_about_anchor()`.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// // A tree which grows out of the ground.
+    /// app.draw(Rect([-0.05, -0.5], [0.05, 0.0]).scale_about_anchor(Anchor::BottomCenter, 1.5));
+    /// ```
+    #[inline]
+    fn scale_about_anchor(&self, anchor: Anchor, scale: f32) -> Transformed<Self> where Self: Clone {
+        self.scale_about(anchor_point(self, anchor), scale)
+    }
+
+    /// The smallest rectangle which contains the shape, with the smallest coordinates first, or
+    /// `None` if the shape has no triangles.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use nest::*;
+    /// let bounds = rect([-0.5, -0.5], [0.5, 0.5]).translate([0.5, 0.0]).bounds().unwrap();
+    /// assert_eq!((bounds.0, bounds.1), ([0.0, -0.5], [1.0, 0.5]));
+    /// ```
+    #[inline]
+    fn bounds(&self) -> Option<Rect> where Self: Clone {
+        anchor::bounds(self.clone())
+    }
+
+    /// Transform a shape with an affine `Transform`. Nested transformations are combined into one.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// app.draw(Rect([-0.5, -0.5], [0.5, 0.5]).transform(Transform::rotation(0.5).scale(0.5)));
+    /// ```
+    #[inline]
+    fn transform(&self, transform: Transform) -> Transformed<Self> where Self: Clone {
+        Transformed::new(self.clone(), transform)
+    }
+
+    /// Shear a shape, moving `x` by `shear.x * y` and `y` by `shear.y * x`.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// // An isometric floor tile.
+    /// app.draw(Rect([-0.5, -0.5], [0.5, 0.5]).rotate(0.785).scale_y(0.5).shear([0.2, 0.0]));
+    /// ```
+    #[inline]
+    fn shear<V: Into<cgm::Vector2<f32>>>(&self, shear: V) -> Transformed<Self> where Self: Clone {
+        Transformed::new(self.clone(), Transform::shearing(shear))
+    }
+
+    /// Skew a shape horizontally using an angle in radians, so that vertical lines lean to the
+    /// right for positive angles.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// let font = Font::open("examples/DejaVuSansMono.ttf").unwrap();
+    /// // Italic text.
+    /// app.draw(text(&font, "Hello", 0.2).skew_x(0.2));
+    /// ```
+    #[inline]
+    fn skew_x(&self, angle: f32) -> Transformed<Self> where Self: Clone {
+        Transformed::new(self.clone(), Transform::IDENTITY.skew_x(angle))
+    }
+
+    /// Skew a shape vertically using an angle in radians, so that horizontal lines lean upwards
+    /// for positive angles.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// app.draw(Rect([-0.5, -0.5], [0.5, 0.5]).skew_y(0.3));
+    /// ```
+    #[inline]
+    fn skew_y(&self, angle: f32) -> Transformed<Self> where Self: Clone {
+        Transformed::new(self.clone(), Transform::IDENTITY.skew_y(angle))
+    }
+
+    /// Mirror a shape from left to right across the `y` axis. Unlike `flip_x()`, this moves the
+    /// shape and its texture together.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// let sprite = app.load_texture("sprite.png").unwrap();
+    /// // A sprite which faces the other way.
+    /// app.draw(image([0.2, -0.2], [0.6, 0.2], sprite).reflect_x());
+    /// ```
+    #[inline]
+    fn reflect_x(&self) -> Transformed<Self> where Self: Clone {
+        Transformed::new(self.clone(), Transform::IDENTITY.reflect_x())
+    }
+
+    /// Mirror a shape from top to bottom across the `x` axis. See `reflect_x()`.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// app.draw(Rect([-0.5, 0.1], [0.5, 0.5]).reflect_y());
+    /// ```
+    #[inline]
+    fn reflect_y(&self) -> Transformed<Self> where Self: Clone {
+        Transformed::new(self.clone(), Transform::IDENTITY.reflect_y())
+    }
+
+    /// Mirror a shape across the line through the points `a` and `b`.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// let half = Rect([-0.5, -0.5], [0.0, 0.5]);
+    /// app.draw(half.combine(half.reflect_across([0.0, -1.0], [0.0, 1.0])));
+    /// ```
+    #[inline]
+    fn reflect_across<P: Into<cgm::Point2<f32>>>(&self, a: P, b: P) -> Transformed<Self> where Self: Clone {
+        Transformed::new(self.clone(), Transform::reflection(a, b))
     }
 
     /// Completely recolor a shape.
@@ -188,15 +389,141 @@ pub trait Shape: IntoIterator<Item = RendTri> {
     fn scale_alpha(&self, scale: f32) -> Mulcolor<Self> where Self: Clone {
         Mulcolor::new(self.clone(), Color([1.0, 1.0, 1.0, scale]))
     }
+
+    /// Draw the textures of the shape with different filtering, wrapping and mipmap options.
+    /// See `Sampler`.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// let sprite = app.load_texture("sprite.png").unwrap();
+    /// app.draw(image([-0.5, -0.5], [0.5, 0.5], sprite).sampler(Sampler::nearest().wrap(Wrap::Clamp)));
+    /// ```
+    #[inline]
+    fn sampler(&self, sampler: Sampler) -> Resample<Self> where Self: Clone {
+        Resample::new(self.clone(), sampler)
+    }
+
+    /// Fill the shape with a texture which repeats across space, so that it tiles seamlessly
+    /// however the shape is sized. Each repetition is `scale` large and one of them has its top
+    /// left corner at `offset`.
+    ///
+    /// The texture coordinates are generated from the positions the shape has here, so
+    /// transformations applied afterwards move the texture along with the shape.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// let bricks = app.load_texture("bricks.png").unwrap();
+    /// // A floor with a brick every 0.2 units.
+    /// app.draw(rect([-1.0, -1.0], [1.0, -0.6]).fill_texture(bricks, [0.2, 0.2], [0.0, 0.0]));
+    /// ```
+    #[inline]
+    fn fill_texture<T, V, O>(&self, texture: T, scale: V, offset: O) -> FillTexture<Self>
+    where
+        Self: Clone,
+        T: Into<Texture>,
+        V: Into<cgm::Vector2<f32>>,
+        O: Into<cgm::Vector2<f32>>,
+    {
+        FillTexture::new(self.clone(), texture.into(), scale.into(), offset.into())
+    }
+
+    /// Translate the texture coordinates of a shape, which moves its texture the opposite way.
+    /// Texture coordinates go from `0.0` to `1.0` across the texture, with `y` pointing down.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// use std::time::Instant;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// let clouds = app.load_texture("clouds.png").unwrap();
+    /// let start = Instant::now();
+    /// // Scroll the clouds to the left.
+    /// app.draw(image([-1.0, -1.0], [1.0, 1.0], clouds).translate_uv([start.elapsed().to_secs() * 0.1, 0.0]));
+    /// ```
+    #[inline]
+    fn translate_uv<V: Into<cgm::Vector2<f32>>>(&self, vector: V) -> TranslateUv<Self> where Self: Clone {
+        TranslateUv::new(self.clone(), vector.into())
+    }
+
+    /// Rotate the texture coordinates of a shape around `[0.0, 0.0]` using an angle in radians.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// use std::f32::consts::PI;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// let city = app.load_texture("examples/city.jpg").unwrap();
+    /// app.draw(image([-0.5, -0.5], [0.5, 0.5], city).rotate_uv(PI / 4.0));
+    /// ```
+    #[inline]
+    fn rotate_uv(&self, angle: f32) -> RotateUv<Self> where Self: Clone {
+        RotateUv::new(self.clone(), angle)
+    }
+
+    /// Scale the texture coordinates of a shape, which repeats its texture `scale` times.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// let city = app.load_texture("examples/city.jpg").unwrap();
+    /// app.draw(image([-0.5, -0.5], [0.5, 0.5], city).scale_uv([2.0, 2.0]));
+    /// ```
+    #[inline]
+    fn scale_uv<V: Into<cgm::Vector2<f32>>>(&self, scale: V) -> ScaleUv<Self> where Self: Clone {
+        ScaleUv::new(self.clone(), scale.into())
+    }
+
+    /// Mirror the texture of a shape from left to right.
+    ///
+    /// Each connected piece of the shape is mirrored around the middle of its own texture
+    /// coordinates, so a region of a texture, such as a frame of a sprite sheet or a glyph of a
+    /// text, stays in place.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// let city = app.load_texture("examples/city.jpg").unwrap();
+    /// app.draw(image([-0.5, -0.5], [0.5, 0.5], city).flip_x());
+    /// ```
+    #[inline]
+    fn flip_x(&self) -> Flip<Self> where Self: Clone {
+        Flip::new(self.clone(), true, false)
+    }
+
+    /// Mirror the texture of a shape from top to bottom. See `flip_x()`.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use nest::*;
+    /// let mut app = Window::new("Example", 640, 480).unwrap();
+    /// let city = app.load_texture("examples/city.jpg").unwrap();
+    /// app.draw(image([-0.5, -0.5], [0.5, 0.5], city).flip_y());
+    /// ```
+    #[inline]
+    fn flip_y(&self) -> Flip<Self> where Self: Clone {
+        Flip::new(self.clone(), false, true)
+    }
 }
 
 impl<S> Shape for S where S: IntoIterator<Item = RendTri> {}
 
+/// The position of `anchor` on the bounding box of `shape`, or the origin if it is empty.
+fn anchor_point<S: Shape + Clone>(shape: &S, anchor: Anchor) -> cgm::Point2<f32> {
+    shape.bounds().map(|b| anchor.point(b)).unwrap_or(cgm::Point2::new(0.0, 0.0))
+}
+
 /// Renderable triangle which includes color and texture information.
 #[derive(Clone, Debug)]
 pub struct RendTri {
     pub(crate) tri: Tri,
-    pub(crate) texture: Option<Rc<Texture2d>>,
+    pub(crate) texture: Option<Texture>,
+    pub(crate) sampler: Sampler,
 }
 
 impl RendTri {
@@ -219,10 +546,26 @@ impl RendTri {
     }
 
     #[inline]
-    fn map_texture<T: Into<Option<Rc<Texture2d>>>>(mut self, t: T) -> RendTri {
+    fn map_texture<T: Into<Option<Texture>>>(mut self, t: T) -> RendTri {
         self.texture = t.into();
         self
     }
+
+    #[inline]
+    fn map_sampler(mut self, sampler: Sampler) -> RendTri {
+        self.sampler = sampler;
+        self
+    }
+
+    /// Whether `other` can be drawn in the same draw call, because both triangles use the same
+    /// texture and sampler or neither has a texture.
+    pub(crate) fn same_batch(&self, other: &RendTri) -> bool {
+        match (self.texture.as_ref(), other.texture.as_ref()) {
+            (Some(a), Some(b)) => a.ptr_eq(b) && self.sampler == other.sampler,
+            (None, None) => true,
+            _ => false,
+        }
+    }
 }
 
 impl From<Tri> for RendTri {
@@ -231,6 +574,7 @@ impl From<Tri> for RendTri {
         RendTri {
             tri: tri,
             texture: None,
+            sampler: Sampler::default(),
         }
     }
 }
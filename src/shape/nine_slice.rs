@@ -0,0 +1,140 @@
+use ::*;
+use std::vec;
+
+/// How the edges and the center of a `NineSlice` fill their space.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SliceFill {
+    /// Stretch the slice over the space.
+    Stretch,
+    /// Repeat the slice at its original size, cutting off the last repetition.
+    Tile,
+}
+
+/// An image which is split into a grid of nine slices by four insets, so that it can be resized
+/// without distorting its borders.
+///
+/// The corners keep their size, the top and bottom edges only grow horizontally, the left and
+/// right edges only vertically and the center in both directions.
+///
+/// Created by `nine_slice()`.
+#[derive(Clone, Debug)]
+pub struct NineSlice {
+    rect: Rect,
+    region: TextureRegion,
+    insets: [u32; 4],
+    scale: f32,
+    fill: SliceFill,
+}
+
+impl NineSlice {
+    /// Draw each pixel of the texture `scale` large. The default is the size of a pixel on a 480
+    /// pixel high window.
+    #[inline]
+    pub fn scale(mut self, scale: f32) -> NineSlice {
+        self.scale = scale;
+        self
+    }
+
+    /// Choose how the edges and the center fill their space. The default is
+    /// `SliceFill::Stretch`.
+    #[inline]
+    pub fn fill(mut self, fill: SliceFill) -> NineSlice {
+        self.fill = fill;
+        self
+    }
+}
+
+/// A piece of a row or column of slices: where it starts and ends, and which texture
+/// coordinates it starts and ends at.
+type Span = (f32, f32, f32, f32);
+
+impl IntoIterator for NineSlice {
+    type IntoIter = vec::IntoIter<RendTri>;
+    type Item = RendTri;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let (min, max) = (self.rect.0, self.rect.1);
+        let (left, right) = (min[0].min(max[0]), min[0].max(max[0]));
+        let (bottom, top) = (min[1].min(max[1]), min[1].max(max[1]));
+        let (uv_min, uv_max) = self.region.uv();
+        let (width, height) = (self.region.width(), self.region.height());
+        let i = self.insets;
+        let tile = self.fill == SliceFill::Tile;
+
+        // Rows are measured downwards from the top, like the texture coordinates.
+        let columns = spans(right - left, [i[0], i[2]], width, [uv_min[0], uv_max[0]], self.scale, tile);
+        let rows = spans(top - bottom, [i[1], i[3]], height, [uv_min[1], uv_max[1]], self.scale, tile);
+
+        let texture = self.region.texture();
+        let mut tris = Vec::with_capacity(columns.len() * rows.len() * 2);
+        for &(y0, y1, v0, v1) in &rows {
+            for &(x0, x1, u0, u1) in &columns {
+                tris.extend(image_region(
+                    [left + x0, top - y1],
+                    [left + x1, top - y0],
+                    TextureRegion::new(texture.clone(), [u0, v0], [u1, v1]),
+                ));
+            }
+        }
+        tris.into_iter()
+    }
+}
+
+/// Split a row or column of `length` into its pieces. `insets` are the sizes of the borders and
+/// `size` the size of the whole region in pixels, and `uv` are the texture coordinates at the
+/// ends of the region.
+fn spans(length: f32, insets: [u32; 2], size: f32, uv: [f32; 2], scale: f32, tile: bool) -> Vec<Span> {
+    let (a, b) = (insets[0] as f32, insets[1] as f32);
+    let (a, b) = if a + b > size { (size * a / (a + b), size * b / (a + b)) } else { (a, b) };
+    let per_pixel = if size > 0.0 { (uv[1] - uv[0]) / size } else { 0.0 };
+    let (ua, ub) = (uv[0] + a * per_pixel, uv[1] - b * per_pixel);
+
+    // The borders shrink if they do not fit.
+    let (mut wa, mut wb) = (a * scale, b * scale);
+    if wa + wb > length {
+        let shrink = if wa + wb > 0.0 { length / (wa + wb) } else { 0.0 };
+        wa *= shrink;
+        wb *= shrink;
+    }
+
+    let mut spans = vec![(0.0, wa, uv[0], ua)];
+    let (start, end) = (wa, length - wb);
+    let tile_length = (size - a - b) * scale;
+    if tile && tile_length > 0.0 && end > start {
+        let mut d = start;
+        while d < end {
+            let next = (d + tile_length).min(end);
+            spans.push((d, next, ua, ua + (ub - ua) * (next - d) / tile_length));
+            d = next;
+        }
+    } else {
+        spans.push((start, end, ua, ub));
+    }
+    spans.push((end, length, ub, uv[1]));
+    spans.retain(|s| s.1 > s.0);
+    spans
+}
+
+/// Takes two points, a texture and the insets of its borders in pixels, in the order left, top,
+/// right and bottom, and draws the texture as a nine-slice on the rectangle specified by the two
+/// points.
+///
+/// ## Example
+/// ```rust,no_run
+/// use nest::*;
+/// let mut app = Window::new("Example", 640, 480).unwrap();
+/// let panel = app.load_texture("panel.png").unwrap();
+/// app.draw(nine_slice([-0.8, -0.5], [0.8, 0.5], panel, [6, 6, 6, 6]).fill(SliceFill::Tile));
+/// ```
+#[inline]
+pub fn nine_slice<A, B, R>(first: A, second: B, region: R, insets: [u32; 4]) -> NineSlice
+    where A: Into<cgm::Point2<f32>>, B: Into<cgm::Point2<f32>>, R: Into<TextureRegion>
+{
+    NineSlice {
+        rect: Rect(first.into().into(), second.into().into()),
+        region: region.into(),
+        insets: insets,
+        scale: 2.0 / 480.0,
+        fill: SliceFill::Stretch,
+    }
+}
@@ -0,0 +1,313 @@
+use ::*;
+use std::f32::consts::PI;
+use std::mem;
+use super::{fill, stroke};
+use super::ellipse::DEFAULT_TOLERANCE;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Segment {
+    Move([f32; 2]),
+    Line([f32; 2]),
+    Quad([f32; 2], [f32; 2]),
+    Cubic([f32; 2], [f32; 2], [f32; 2]),
+    Arc {
+        radii: [f32; 2],
+        rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        to: [f32; 2],
+    },
+    Close,
+}
+
+/// An outline made of lines and curves which can be filled or stroked.
+///
+/// Curves are flattened into lines which stay within the tolerance of the path, see
+/// `Path::tolerance()`.
+///
+/// # Example
+/// ```rust
+/// use nest::*;
+/// // A rounded triangle.
+/// let triangle = Path::new()
+///     .move_to([-0.5, -0.5])
+///     .line_to([0.5, -0.5])
+///     .quad_to([0.5, 0.5], [0.0, 0.5])
+///     .close();
+/// let shape = triangle.fill(FillRule::NonZero)
+///     .combine(triangle.stroke(Stroke::new(0.02)).recolor(Color::RED));
+/// assert!(shape.into_iter().count() > 0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path {
+    segments: Vec<Segment>,
+    tolerance: f32,
+}
+
+impl Default for Path {
+    fn default() -> Path {
+        Path::new()
+    }
+}
+
+impl Path {
+    /// Create an empty path.
+    #[inline]
+    pub fn new() -> Path {
+        Path {
+            segments: Vec::new(),
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn push(&mut self, segment: Segment) {
+        self.segments.push(segment);
+    }
+
+    /// Start a new outline at `to`.
+    #[inline]
+    pub fn move_to<P: Into<cgm::Point2<f32>>>(mut self, to: P) -> Path {
+        self.segments.push(Segment::Move(to.into().into()));
+        self
+    }
+
+    /// Add a straight line to `to`.
+    #[inline]
+    pub fn line_to<P: Into<cgm::Point2<f32>>>(mut self, to: P) -> Path {
+        self.segments.push(Segment::Line(to.into().into()));
+        self
+    }
+
+    /// Add a quadratic Bézier curve to `to` which is pulled towards `control`.
+    #[inline]
+    pub fn quad_to<C, P>(mut self, control: C, to: P) -> Path
+    where
+        C: Into<cgm::Point2<f32>>,
+        P: Into<cgm::Point2<f32>>,
+    {
+        self.segments.push(Segment::Quad(control.into().into(), to.into().into()));
+        self
+    }
+
+    /// Add a cubic Bézier curve to `to` which leaves towards `control1` and arrives from
+    /// `control2`.
+    #[inline]
+    pub fn cubic_to<C1, C2, P>(mut self, control1: C1, control2: C2, to: P) -> Path
+    where
+        C1: Into<cgm::Point2<f32>>,
+        C2: Into<cgm::Point2<f32>>,
+        P: Into<cgm::Point2<f32>>,
+    {
+        self.segments.push(Segment::Cubic(
+            control1.into().into(),
+            control2.into().into(),
+            to.into().into(),
+        ));
+        self
+    }
+
+    /// Add part of an ellipse to `to`, like the SVG arc command.
+    ///
+    /// The ellipse has the x and y `radii` and is rotated by `rotation` radians. Of the four arcs
+    /// which connect the points, `large_arc` picks one of the two longer ones and `sweep` picks
+    /// one going counter-clockwise. The radii are scaled up if the points are too far apart.
+    #[inline]
+    pub fn arc_to<R, P>(mut self, radii: R, rotation: f32, large_arc: bool, sweep: bool, to: P) -> Path
+    where
+        R: Into<cgm::Vector2<f32>>,
+        P: Into<cgm::Point2<f32>>,
+    {
+        let radii = radii.into();
+        self.segments.push(Segment::Arc {
+            radii: [radii.x, radii.y],
+            rotation: rotation,
+            large_arc: large_arc,
+            sweep: sweep,
+            to: to.into().into(),
+        });
+        self
+    }
+
+    /// Connect the current outline back to its start. Drawing continues from there.
+    #[inline]
+    pub fn close(mut self) -> Path {
+        self.segments.push(Segment::Close);
+        self
+    }
+
+    /// Change the distance the flattened lines may stray from the curves.
+    #[inline]
+    pub fn tolerance(mut self, tolerance: f32) -> Path {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Fill the inside of the path. Outlines which are not closed are treated as if they were.
+    pub fn fill(&self, rule: FillRule) -> Polygon {
+        let outlines = self.flatten()
+            .into_iter()
+            .map(|(points, _)| points)
+            .collect::<Vec<_>>();
+        Polygon::from_tris(fill::fill(&outlines, rule))
+    }
+
+    /// Draw a line along the path.
+    pub fn stroke(&self, style: Stroke) -> Polygon {
+        let mut tris = Vec::new();
+        for (points, closed) in self.flatten() {
+            tris.extend(stroke::stroke(&points, closed, &style));
+        }
+        Polygon::from_tris(tris)
+    }
+
+    /// Flatten the path into outlines made of straight lines, and whether each one is closed.
+    pub(crate) fn flatten(&self) -> Vec<(Vec<[f32; 2]>, bool)> {
+        let mut outlines = Vec::new();
+        let mut points: Vec<[f32; 2]> = Vec::new();
+        // The first point of the current outline.
+        let mut start = [0.0, 0.0];
+
+        for &segment in &self.segments {
+            match segment {
+                Segment::Move(to) => {
+                    if points.len() > 1 {
+                        outlines.push((mem::replace(&mut points, Vec::new()), false));
+                    }
+                    points = vec![to];
+                    start = to;
+                    continue;
+                }
+                Segment::Close => {
+                    if points.len() > 1 {
+                        outlines.push((mem::replace(&mut points, Vec::new()), true));
+                    }
+                    points.clear();
+                    continue;
+                }
+                _ => {}
+            }
+
+            // Drawing after `close()` or without `move_to()` continues from the start point.
+            if points.is_empty() {
+                points.push(start);
+            }
+            let from = points[points.len() - 1];
+            match segment {
+                Segment::Line(to) => points.push(to),
+                Segment::Quad(c, to) => {
+                    let dd = length(add(sub(from, scale(c, 2.0)), to));
+                    let n = subdivisions(dd / 4.0, self.tolerance);
+                    for i in 1..n + 1 {
+                        let t = i as f32 / n as f32;
+                        let s = 1.0 - t;
+                        points.push(add(add(scale(from, s * s), scale(c, 2.0 * s * t)), scale(to, t * t)));
+                    }
+                }
+                Segment::Cubic(c1, c2, to) => {
+                    let dd = length(add(sub(from, scale(c1, 2.0)), c2))
+                        .max(length(add(sub(c1, scale(c2, 2.0)), to)));
+                    let n = subdivisions(dd * 0.75, self.tolerance);
+                    for i in 1..n + 1 {
+                        let t = i as f32 / n as f32;
+                        let s = 1.0 - t;
+                        points.push(add(
+                            add(scale(from, s * s * s), scale(c1, 3.0 * s * s * t)),
+                            add(scale(c2, 3.0 * s * t * t), scale(to, t * t * t)),
+                        ));
+                    }
+                }
+                Segment::Arc { radii, rotation, large_arc, sweep, to } => {
+                    arc(from, radii, rotation, large_arc, sweep, to, self.tolerance, &mut points)
+                }
+                Segment::Move(_) | Segment::Close => unreachable!(),
+            }
+        }
+        if points.len() > 1 {
+            outlines.push((points, false));
+        }
+        outlines
+    }
+}
+
+/// The number of lines needed to keep a curve within `tolerance`, where the chord error of a
+/// single line is `error`.
+#[inline]
+fn subdivisions(error: f32, tolerance: f32) -> usize {
+    if !(tolerance > 0.0) || !(error > tolerance) {
+        return 1;
+    }
+    ((error / tolerance).sqrt().ceil() as usize).max(1)
+}
+
+/// Flatten an SVG style elliptical arc using the conversion to center parameterization from
+/// the SVG specification.
+fn arc(from: [f32; 2], radii: [f32; 2], rotation: f32, large_arc: bool, sweep: bool, to: [f32; 2], tolerance: f32, points: &mut Vec<[f32; 2]>) {
+    let (mut rx, mut ry) = (radii[0].abs(), radii[1].abs());
+    if from == to {
+        return;
+    }
+    if rx == 0.0 || ry == 0.0 {
+        points.push(to);
+        return;
+    }
+    let (sin, cos) = rotation.sin_cos();
+    let hx = (from[0] - to[0]) / 2.0;
+    let hy = (from[1] - to[1]) / 2.0;
+    let x1 = cos * hx + sin * hy;
+    let y1 = -sin * hx + cos * hy;
+
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+    let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut coef = (num / den).max(0.0).sqrt();
+    if large_arc == sweep {
+        coef = -coef;
+    }
+    let cx1 = coef * rx * y1 / ry;
+    let cy1 = -coef * ry * x1 / rx;
+    let cx = cos * cx1 - sin * cy1 + (from[0] + to[0]) / 2.0;
+    let cy = sin * cx1 + cos * cy1 + (from[1] + to[1]) / 2.0;
+
+    let angle = |x: f32, y: f32| y.atan2(x);
+    let start = angle((x1 - cx1) / rx, (y1 - cy1) / ry);
+    let mut delta = angle((-x1 - cx1) / rx, (-y1 - cy1) / ry) - start;
+    if sweep && delta < 0.0 {
+        delta += 2.0 * PI;
+    } else if !sweep && delta > 0.0 {
+        delta -= 2.0 * PI;
+    }
+
+    let n = Detail::Tolerance(tolerance).segments(rx.max(ry), delta);
+    for i in 1..n {
+        let t = start + delta * i as f32 / n as f32;
+        let (x, y) = (rx * t.cos(), ry * t.sin());
+        points.push([cx + cos * x - sin * y, cy + sin * x + cos * y]);
+    }
+    // The end point is pushed exactly so that following segments connect without a gap.
+    points.push(to);
+}
+
+#[inline]
+fn add(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] + b[0], a[1] + b[1]]
+}
+
+#[inline]
+fn sub(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] - b[0], a[1] - b[1]]
+}
+
+#[inline]
+fn scale(a: [f32; 2], s: f32) -> [f32; 2] {
+    [a[0] * s, a[1] * s]
+}
+
+#[inline]
+fn length(a: [f32; 2]) -> f32 {
+    (a[0] * a[0] + a[1] * a[1]).sqrt()
+}
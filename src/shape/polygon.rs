@@ -0,0 +1,347 @@
+use ::*;
+use std::rc::Rc;
+
+/// A filled polygon, which may be concave and may have holes.
+///
+/// The polygon is triangulated once when it is created, so cloning and drawing it is cheap.
+/// Created by `polygon()` and `polygon_with_holes()`, and by filling or stroking a `Path`.
+#[derive(Clone, Debug)]
+pub struct Polygon {
+    tris: Rc<Vec<Tri>>,
+}
+
+impl Polygon {
+    pub(crate) fn from_tris(tris: Vec<[[f32; 2]; 3]>) -> Polygon {
+        Polygon { tris: Rc::new(tris.into_iter().map(Tri::new_pos).collect()) }
+    }
+}
+
+impl IntoIterator for Polygon {
+    type IntoIter = PolygonIter;
+    type Item = RendTri;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        PolygonIter {
+            tris: self.tris,
+            index: 0,
+        }
+    }
+}
+
+/// Iterator which is produced by `Polygon`
+#[derive(Clone, Debug)]
+pub struct PolygonIter {
+    tris: Rc<Vec<Tri>>,
+    index: usize,
+}
+
+impl Iterator for PolygonIter {
+    type Item = RendTri;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let tri = self.tris.get(self.index).cloned();
+        self.index += 1;
+        tri.map(RendTri::from)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.tris.len().saturating_sub(self.index);
+        (len, Some(len))
+    }
+}
+
+/// Takes the points along the outline of a polygon in either winding order and creates a filled
+/// polygon. The outline does not need to be convex, but it must not intersect itself.
+///
+/// ## Example
+/// ```rust
+/// use nest::*;
+/// // An arrow pointing right.
+/// let arrow = polygon(&[
+///     [-0.5, 0.1], [0.2, 0.1], [0.2, 0.3], [0.5, 0.0],
+///     [0.2, -0.3], [0.2, -0.1], [-0.5, -0.1],
+/// ]).unwrap();
+/// assert_eq!(arrow.into_iter().count(), 5);
+///
+/// // Self-intersecting outlines are rejected.
+/// assert!(polygon(&[[0.0, 0.0], [1.0, 1.0], [1.0, 0.0], [0.0, 1.0]]).is_err());
+/// ```
+pub fn polygon<P: Into<cgm::Point2<f32>> + Copy>(outline: &[P]) -> Result<Polygon> {
+    polygon_with_holes::<P, &[P]>(outline, &[])
+}
+
+/// Takes the outline of a polygon and the outlines of any holes in it and creates a filled
+/// polygon. None of the outlines may intersect themselves or each other, and every hole must be
+/// inside of the outer outline.
+///
+/// ## Example
+/// ```rust
+/// use nest::*;
+/// let frame = polygon_with_holes(
+///     &[[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5], [-0.5, 0.5]],
+///     &[&[[-0.25, -0.25], [0.25, -0.25], [0.25, 0.25], [-0.25, 0.25]][..]],
+/// ).unwrap();
+/// assert_eq!(frame.into_iter().count(), 8);
+/// ```
+pub fn polygon_with_holes<P, H>(outline: &[P], holes: &[H]) -> Result<Polygon>
+where
+    P: Into<cgm::Point2<f32>> + Copy,
+    H: AsRef<[P]>,
+{
+    let outer = ring(outline)?;
+    let holes = holes
+        .iter()
+        .map(|h| ring(h.as_ref()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Polygon::from_tris(triangulate(outer, holes)?))
+}
+
+/// Convert an outline into a list of points without repeated points.
+fn ring<P: Into<cgm::Point2<f32>> + Copy>(points: &[P]) -> Result<Vec<[f32; 2]>> {
+    let mut ring: Vec<[f32; 2]> = Vec::with_capacity(points.len());
+    for &p in points {
+        let p: [f32; 2] = p.into().into();
+        if !p[0].is_finite() || !p[1].is_finite() {
+            bail!(ErrorKind::InvalidPolygon("outline contains a point which is not finite"));
+        }
+        if ring.last() != Some(&p) {
+            ring.push(p);
+        }
+    }
+    // The outline may be explicitly closed.
+    while ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    if ring.len() < 3 || area(&ring) == 0.0 {
+        bail!(ErrorKind::InvalidPolygon("outline has no area"));
+    }
+    Ok(ring)
+}
+
+/// Triangulate a polygon with holes.
+pub(crate) fn triangulate(mut outer: Vec<[f32; 2]>, mut holes: Vec<Vec<[f32; 2]>>) -> Result<Vec<[[f32; 2]; 3]>> {
+    check_intersections(&outer, &holes)?;
+    for (i, hole) in holes.iter().enumerate() {
+        if !inside(&outer, hole[0]) {
+            bail!(ErrorKind::InvalidPolygon("hole is outside of the outline"));
+        }
+        if holes.iter().enumerate().any(|(j, other)| i != j && inside(other, hole[0])) {
+            bail!(ErrorKind::InvalidPolygon("hole is inside of another hole"));
+        }
+    }
+
+    // The outline goes counter-clockwise and holes go clockwise so the merged outline
+    // always has the inside of the polygon to its left.
+    if area(&outer) < 0.0 {
+        outer.reverse();
+    }
+    for hole in &mut holes {
+        if area(hole) > 0.0 {
+            hole.reverse();
+        }
+    }
+
+    // Holes are bridged to the outline from right to left so that a bridge never needs to
+    // cross a hole which has not been merged yet.
+    holes.sort_by(|a, b| max_x(b).partial_cmp(&max_x(a)).unwrap());
+    for i in 0..holes.len() {
+        outer = bridge(outer, &holes[i], &holes[i + 1..])?;
+    }
+
+    ear_clip(outer)
+}
+
+/// Merge `hole` into `outer` by connecting them with a pair of coincident edges.
+fn bridge(outer: Vec<[f32; 2]>, hole: &[[f32; 2]], remaining: &[Vec<[f32; 2]>]) -> Result<Vec<[f32; 2]>> {
+    let hi = (0..hole.len())
+        .max_by(|&a, &b| hole[a][0].partial_cmp(&hole[b][0]).unwrap())
+        .unwrap();
+    let m = hole[hi];
+
+    let mut candidates: Vec<usize> = (0..outer.len()).collect();
+    candidates.sort_by(|&a, &b| dist2(m, outer[a]).partial_cmp(&dist2(m, outer[b])).unwrap());
+    let visible = candidates.into_iter().find(|&oi| {
+        let v = outer[oi];
+        let mid = [(m[0] + v[0]) / 2.0, (m[1] + v[1]) / 2.0];
+        !crosses_ring(&outer, m, v)
+            && !crosses_ring(hole, m, v)
+            && !remaining.iter().any(|r| crosses_ring(r, m, v))
+            && inside(&outer, mid)
+            && !inside(hole, mid)
+    });
+    let oi = match visible {
+        Some(oi) => oi,
+        None => bail!(ErrorKind::InvalidPolygon("hole could not be connected to the outline")),
+    };
+
+    let mut merged = Vec::with_capacity(outer.len() + hole.len() + 2);
+    merged.extend_from_slice(&outer[..oi + 1]);
+    merged.extend_from_slice(&hole[hi..]);
+    merged.extend_from_slice(&hole[..hi + 1]);
+    merged.extend_from_slice(&outer[oi..]);
+    Ok(merged)
+}
+
+/// Triangulate a counter-clockwise outline by repeatedly cutting off ears.
+fn ear_clip(points: Vec<[f32; 2]>) -> Result<Vec<[[f32; 2]; 3]>> {
+    let mut tris = Vec::with_capacity(points.len().saturating_sub(2));
+    let mut ring: Vec<usize> = (0..points.len()).collect();
+    // Ears tend to be found next to the previous one, so the search starts there.
+    let mut start = 0;
+
+    while ring.len() > 3 {
+        let n = ring.len();
+        let corner = |i: usize| {
+            (points[ring[(i + n - 1) % n]], points[ring[i]], points[ring[(i + 1) % n]])
+        };
+        let is_ear = |i: usize| {
+            let (a, b, c) = corner(i);
+            cross(a, b, c) > 0.0
+                && !ring.iter().any(|&j| {
+                    let p = points[j];
+                    p != a && p != b && p != c && in_triangle(p, a, b, c)
+                })
+        };
+
+        let ear = (0..n)
+            .map(|k| (start + k) % n)
+            .find(|&i| is_ear(i))
+            // Straight corners can be removed without adding a triangle.
+            .or_else(|| (0..n).find(|&i| {
+                let (a, b, c) = corner(i);
+                cross(a, b, c) == 0.0
+            }));
+        let i = match ear {
+            Some(i) => i,
+            None => bail!(ErrorKind::InvalidPolygon("outline could not be triangulated")),
+        };
+        let (a, b, c) = corner(i);
+        if cross(a, b, c) != 0.0 {
+            tris.push([a, b, c]);
+        }
+        ring.remove(i);
+        start = i.saturating_sub(1);
+    }
+
+    let (a, b, c) = (points[ring[0]], points[ring[1]], points[ring[2]]);
+    if cross(a, b, c) != 0.0 {
+        tris.push([a, b, c]);
+    }
+    Ok(tris)
+}
+
+/// Fail if any edges of the outlines cross each other.
+fn check_intersections(outer: &[[f32; 2]], holes: &[Vec<[f32; 2]>]) -> Result<()> {
+    let rings: Vec<&[[f32; 2]]> = Some(outer).into_iter().chain(holes.iter().map(|h| &h[..])).collect();
+    for (ri, a) in rings.iter().enumerate() {
+        for i in 0..a.len() {
+            let (a0, a1) = (a[i], a[(i + 1) % a.len()]);
+            for (rj, b) in rings.iter().enumerate().skip(ri) {
+                let first = if ri == rj { i + 1 } else { 0 };
+                for j in first..b.len() {
+                    let (b0, b1) = (b[j], b[(j + 1) % b.len()]);
+                    // Neighbouring edges always share a point.
+                    let neighbours = ri == rj && (j == i + 1 || (i == 0 && j == a.len() - 1));
+                    let hit = if neighbours {
+                        overlapping(a0, a1, b0, b1)
+                    } else {
+                        touching(a0, a1, b0, b1)
+                    };
+                    if hit {
+                        bail!(ErrorKind::InvalidPolygon("outlines are self-intersecting"));
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns true if the segment `ab` crosses or touches an edge of `ring` other than at `a` or `b`.
+fn crosses_ring(ring: &[[f32; 2]], a: [f32; 2], b: [f32; 2]) -> bool {
+    (0..ring.len()).any(|i| {
+        let (c, d) = (ring[i], ring[(i + 1) % ring.len()]);
+        if c == a || c == b || d == a || d == b {
+            // Edges which share an end point can only overlap along a line.
+            overlapping(a, b, c, d)
+        } else {
+            touching(a, b, c, d)
+        }
+    })
+}
+
+/// Returns true if the segments `ab` and `cd` have any point in common.
+fn touching(a: [f32; 2], b: [f32; 2], c: [f32; 2], d: [f32; 2]) -> bool {
+    let (d1, d2) = (cross(a, b, c), cross(a, b, d));
+    let (d3, d4) = (cross(c, d, a), cross(c, d, b));
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0)) && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0)) {
+        return true;
+    }
+    (d1 == 0.0 && on_segment(a, b, c)) || (d2 == 0.0 && on_segment(a, b, d))
+        || (d3 == 0.0 && on_segment(c, d, a)) || (d4 == 0.0 && on_segment(c, d, b))
+}
+
+/// Returns true if the segments `ab` and `cd` are collinear and share more than a single point.
+fn overlapping(a: [f32; 2], b: [f32; 2], c: [f32; 2], d: [f32; 2]) -> bool {
+    if cross(a, b, c) != 0.0 || cross(a, b, d) != 0.0 {
+        return false;
+    }
+    let strictly = |p: [f32; 2], q: [f32; 2], r: [f32; 2]| on_segment(p, q, r) && r != p && r != q;
+    strictly(a, b, c) || strictly(a, b, d) || strictly(c, d, a) || strictly(c, d, b)
+        || (a == c && b == d) || (a == d && b == c)
+}
+
+/// Returns true if `p`, which is collinear with `ab`, lies between `a` and `b`.
+#[inline]
+fn on_segment(a: [f32; 2], b: [f32; 2], p: [f32; 2]) -> bool {
+    p[0] >= a[0].min(b[0]) && p[0] <= a[0].max(b[0]) && p[1] >= a[1].min(b[1]) && p[1] <= a[1].max(b[1])
+}
+
+/// Returns true if `p` is inside of or on the edge of the triangle `abc`.
+#[inline]
+fn in_triangle(p: [f32; 2], a: [f32; 2], b: [f32; 2], c: [f32; 2]) -> bool {
+    cross(a, b, p) >= 0.0 && cross(b, c, p) >= 0.0 && cross(c, a, p) >= 0.0
+}
+
+/// Returns true if `p` is inside of the outline `ring` using the even-odd rule.
+fn inside(ring: &[[f32; 2]], p: [f32; 2]) -> bool {
+    let mut inside = false;
+    let mut j = ring.len() - 1;
+    for i in 0..ring.len() {
+        let (a, b) = (ring[i], ring[j]);
+        if (a[1] > p[1]) != (b[1] > p[1]) && p[0] < (b[0] - a[0]) * (p[1] - a[1]) / (b[1] - a[1]) + a[0] {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Twice the signed area of the outline, which is positive when it goes counter-clockwise.
+fn area(ring: &[[f32; 2]]) -> f32 {
+    let mut sum = 0.0;
+    for i in 0..ring.len() {
+        let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+        sum += a[0] * b[1] - b[0] * a[1];
+    }
+    sum
+}
+
+/// Positive if `abc` turns counter-clockwise, negative if clockwise and zero if straight.
+#[inline]
+fn cross(a: [f32; 2], b: [f32; 2], c: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+#[inline]
+fn dist2(a: [f32; 2], b: [f32; 2]) -> f32 {
+    (a[0] - b[0]) * (a[0] - b[0]) + (a[1] - b[1]) * (a[1] - b[1])
+}
+
+#[inline]
+fn max_x(ring: &[[f32; 2]]) -> f32 {
+    ring.iter().fold(::std::f32::MIN, |m, p| m.max(p[0]))
+}
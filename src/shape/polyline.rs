@@ -0,0 +1,90 @@
+use ::*;
+use std::rc::Rc;
+use std::vec;
+
+/// A line through a list of points with a width, joins and caps.
+///
+/// Created by `polyline()`.
+#[derive(Clone, Debug)]
+pub struct Polyline {
+    points: Rc<Vec<[f32; 2]>>,
+    closed: bool,
+    stroke: Stroke,
+}
+
+impl Polyline {
+    /// Connect the last point back to the first one.
+    #[inline]
+    pub fn closed(mut self) -> Polyline {
+        self.closed = true;
+        self
+    }
+
+    /// Change how segments are connected.
+    #[inline]
+    pub fn join(mut self, join: Join) -> Polyline {
+        self.stroke.join = join;
+        self
+    }
+
+    /// Change how the ends of the line are drawn. Closed lines have no ends.
+    #[inline]
+    pub fn cap(mut self, cap: Cap) -> Polyline {
+        self.stroke.cap = cap;
+        self
+    }
+
+    /// Change the longest a `Join::Miter` may be as a multiple of the line width before a bevel
+    /// is used instead.
+    #[inline]
+    pub fn miter_limit(mut self, limit: f32) -> Polyline {
+        self.stroke.miter_limit = limit;
+        self
+    }
+
+    /// Change how finely round joins and caps are split into triangles.
+    #[inline]
+    pub fn detail(mut self, detail: Detail) -> Polyline {
+        self.stroke.detail = detail;
+        self
+    }
+
+    /// Replace the whole stroke style of the line.
+    #[inline]
+    pub fn stroke(mut self, stroke: Stroke) -> Polyline {
+        self.stroke = stroke;
+        self
+    }
+}
+
+impl IntoIterator for Polyline {
+    type IntoIter = vec::IntoIter<RendTri>;
+    type Item = RendTri;
+
+    fn into_iter(self) -> Self::IntoIter {
+        super::stroke::stroke(&self.points, self.closed, &self.stroke)
+            .into_iter()
+            .map(|t| Tri::new_pos(t).into())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+/// Takes a list of points and a width and creates a line through the points.
+///
+/// ## Example
+/// ```rust,no_run
+/// use nest::*;
+/// let mut app = Window::new("Example", 640, 480).unwrap();
+/// app.draw(polyline(&[[-0.5, -0.5], [0.0, 0.5], [0.5, -0.5]], 0.05)
+///     .join(Join::Round)
+///     .cap(Cap::Round));
+/// ```
+#[inline]
+pub fn polyline<P: Into<cgm::Point2<f32>> + Copy>(points: &[P], width: f32) -> Polyline {
+    Polyline {
+        points: Rc::new(points.iter().map(|&p| p.into().into()).collect()),
+        closed: false,
+        stroke: Stroke::new(width),
+    }
+}
@@ -0,0 +1,51 @@
+use {RendTri, Sampler, Shape};
+
+/// `Resample` represents a shape whose textures are drawn with different sampler options.
+#[derive(Copy, Clone, Debug)]
+pub struct Resample<S> {
+    shape: S,
+    sampler: Sampler,
+}
+
+impl<S> Resample<S> {
+    pub(crate) fn new(shape: S, sampler: Sampler) -> Self {
+        Resample {
+            shape: shape,
+            sampler: sampler,
+        }
+    }
+}
+
+impl<S> IntoIterator for Resample<S>
+where
+    S: Shape,
+{
+    type Item = RendTri;
+    type IntoIter = ResampleIter<S::IntoIter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ResampleIter {
+            iter: self.shape.into_iter(),
+            sampler: self.sampler,
+        }
+    }
+}
+
+/// Iterator which is produced by `Resample`
+#[derive(Clone, Debug)]
+pub struct ResampleIter<I> {
+    iter: I,
+    sampler: Sampler,
+}
+
+impl<I> Iterator for ResampleIter<I>
+where
+    I: Iterator<Item = RendTri>,
+{
+    type Item = RendTri;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sampler = self.sampler;
+        self.iter.next().map(|t| t.map_sampler(sampler))
+    }
+}
@@ -1,45 +1,45 @@
 use {cgm, RendTri, Shape};
 use cgm::{Rad, Rotation, Rotation2};
 
-/// `Rotate` represents a shape which has been rotated.
+/// `RotateUv` represents a shape whose texture coordinates have been rotated.
 #[derive(Copy, Clone, Debug)]
-pub struct Rotate<S> {
+pub struct RotateUv<S> {
     shape: S,
     rotation: cgm::Basis2<f32>,
 }
 
-impl<S> Rotate<S> {
+impl<S> RotateUv<S> {
     pub(crate) fn new(shape: S, angle: f32) -> Self {
-        Rotate {
+        RotateUv {
             shape: shape,
             rotation: cgm::Basis2::from_angle(Rad(angle)),
         }
     }
 }
 
-impl<S> IntoIterator for Rotate<S>
+impl<S> IntoIterator for RotateUv<S>
 where
     S: Shape,
 {
     type Item = RendTri;
-    type IntoIter = RotateIter<S::IntoIter>;
+    type IntoIter = RotateUvIter<S::IntoIter>;
 
     fn into_iter(self) -> Self::IntoIter {
-        RotateIter {
+        RotateUvIter {
             iter: self.shape.into_iter(),
             rotation: self.rotation,
         }
     }
 }
 
-/// Iterator which is produced by `Rotate`
+/// Iterator which is produced by `RotateUv`
 #[derive(Clone, Debug)]
-pub struct RotateIter<I> {
+pub struct RotateUvIter<I> {
     iter: I,
     rotation: cgm::Basis2<f32>,
 }
 
-impl<I> Iterator for RotateIter<I>
+impl<I> Iterator for RotateUvIter<I>
 where
     I: Iterator<Item = RendTri>,
 {
@@ -48,6 +48,6 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         self.iter
             .next()
-            .map(|t| t.map_pos(|p| self.rotation.rotate_point(p)))
+            .map(|t| t.map_tex(|p| self.rotation.rotate_point(p)))
     }
 }
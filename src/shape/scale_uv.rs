@@ -0,0 +1,52 @@
+use {cgm, RendTri, Shape};
+
+/// `ScaleUv` represents a shape whose texture coordinates have been scaled.
+#[derive(Copy, Clone, Debug)]
+pub struct ScaleUv<S> {
+    shape: S,
+    scale: cgm::Vector2<f32>,
+}
+
+impl<S> ScaleUv<S> {
+    pub(crate) fn new(shape: S, scale: cgm::Vector2<f32>) -> Self {
+        ScaleUv {
+            shape: shape,
+            scale: scale,
+        }
+    }
+}
+
+impl<S> IntoIterator for ScaleUv<S>
+where
+    S: Shape,
+{
+    type Item = RendTri;
+    type IntoIter = ScaleUvIter<S::IntoIter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ScaleUvIter {
+            iter: self.shape.into_iter(),
+            scale: self.scale,
+        }
+    }
+}
+
+/// Iterator which is produced by `ScaleUv`
+#[derive(Clone, Debug)]
+pub struct ScaleUvIter<I> {
+    iter: I,
+    scale: cgm::Vector2<f32>,
+}
+
+impl<I> Iterator for ScaleUvIter<I>
+where
+    I: Iterator<Item = RendTri>,
+{
+    type Item = RendTri;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|t| t.map_tex(|p| cgm::Point2::new(p.x * self.scale.x, p.y * self.scale.y)))
+    }
+}
@@ -0,0 +1,255 @@
+use ::*;
+use std::f32::consts::PI;
+use super::ellipse::DEFAULT_TOLERANCE;
+
+/// How two segments of a stroke are connected.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Join {
+    /// Extend the outer edges of the segments until they meet in a point. Falls back to `Bevel`
+    /// when the point would be further away than the miter limit allows.
+    Miter,
+    /// Connect the segments with a circular arc.
+    Round,
+    /// Connect the outer corners of the segments with a straight edge.
+    Bevel,
+}
+
+/// How the ends of an open stroke are drawn.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Cap {
+    /// End exactly at the end point.
+    Butt,
+    /// Extend past the end point by half of the line width.
+    Square,
+    /// End with a half circle around the end point.
+    Round,
+}
+
+/// Describes how a line is turned into a filled shape.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Stroke {
+    /// The width of the line.
+    pub width: f32,
+    /// How segments are connected.
+    pub join: Join,
+    /// How the ends of open lines are drawn.
+    pub cap: Cap,
+    /// The longest a `Join::Miter` may be as a multiple of the line width.
+    pub miter_limit: f32,
+    /// How finely round joins and caps are split into triangles.
+    pub detail: Detail,
+}
+
+impl Stroke {
+    /// Create a stroke of `width` with miter joins, butt caps and a miter limit of 4.
+    #[inline]
+    pub fn new(width: f32) -> Stroke {
+        Stroke {
+            width: width,
+            join: Join::Miter,
+            cap: Cap::Butt,
+            miter_limit: 4.0,
+            detail: Detail::Tolerance(DEFAULT_TOLERANCE),
+        }
+    }
+}
+
+/// Turn a line through `points` into triangles. Closed lines also connect the last point to the
+/// first one.
+///
+/// Where two segments meet, both are cut along the line from the joint to the point where their
+/// inner edges cross, and the join only fills the outside of the turn, so translucent lines are
+/// blended once there. Segments which are too short to be cut like this overlap at the joint.
+pub(crate) fn stroke(points: &[[f32; 2]], closed: bool, style: &Stroke) -> Vec<[[f32; 2]; 3]> {
+    let mut points = points.to_vec();
+    points.dedup();
+    if closed && points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+
+    let mut tris = Vec::new();
+    let hw = style.width / 2.0;
+    if points.len() < 2 || !(hw > 0.0) {
+        return tris;
+    }
+
+    let n = points.len();
+    let segments = if closed && n > 2 { n } else { n - 1 };
+    let dir = |i: usize| normalize(sub(points[(i + 1) % n], points[i]));
+    let length = |i: usize| {
+        let d = sub(points[(i + 1) % n], points[i]);
+        dot(d, d).sqrt()
+    };
+    let joints = if segments == n { 0..n } else { 1..n - 1 };
+
+    let mut inner = vec![None; n];
+    for i in joints.clone() {
+        let prev = (i + n - 1) % n;
+        inner[i] = inner_corner(points[i], dir(prev), dir(i), length(prev).min(length(i)), hw);
+    }
+    // The corners of the end of a segment at point `i`, from its left edge to its right edge.
+    let end = |i: usize, offset: [f32; 2]| {
+        let p = points[i];
+        match inner[i] {
+            Some((q, true)) => vec![q, p, sub(p, offset)],
+            Some((q, false)) => vec![add(p, offset), p, q],
+            None => vec![add(p, offset), sub(p, offset)],
+        }
+    };
+
+    for i in 0..segments {
+        let offset = scale(perp(dir(i)), hw);
+        // The outline is convex, so it can be filled with a fan.
+        let mut outline = end(i, offset);
+        outline.extend(end((i + 1) % n, offset).into_iter().rev());
+        for k in 1..outline.len() - 1 {
+            tris.push([outline[0], outline[k], outline[k + 1]]);
+        }
+    }
+
+    for i in joints {
+        let (d0, d1) = (dir((i + n - 1) % n), dir(i));
+        join(points[i], d0, d1, style, &mut tris);
+    }
+
+    if segments != n {
+        cap(points[0], scale(dir(0), -1.0), style, &mut tris);
+        cap(points[n - 1], dir(n - 2), style, &mut tris);
+    }
+    tris
+}
+
+/// The point where the inner edges of the segments meeting at `p` cross, and whether it is on
+/// their left. Returns `None` if the line doesn't turn or if the segments are cut further than
+/// half of the length `shortest` of the shorter one, as its other end may be cut too.
+fn inner_corner(p: [f32; 2], d0: [f32; 2], d1: [f32; 2], shortest: f32, hw: f32) -> Option<([f32; 2], bool)> {
+    let turn = cross(d0, d1);
+    if turn == 0.0 {
+        return None;
+    }
+    // The inside of a left turn is on the left.
+    let left = turn > 0.0;
+    let side = if left { hw } else { -hw };
+    let (i0, i1) = (scale(perp(d0), side), scale(perp(d1), side));
+    let bisector = normalize(add(i0, i1));
+    let cos_half = dot(bisector, normalize(i0));
+    if !(cos_half > 0.0) {
+        return None;
+    }
+    let tan_half = (1.0 - cos_half * cos_half).max(0.0).sqrt() / cos_half;
+    if !(hw * tan_half <= shortest / 2.0) {
+        return None;
+    }
+    Some((add(p, scale(bisector, hw / cos_half)), left))
+}
+
+/// Fill the gap on the outside of the turn at `p` from direction `d0` to direction `d1`.
+fn join(p: [f32; 2], d0: [f32; 2], d1: [f32; 2], style: &Stroke, tris: &mut Vec<[[f32; 2]; 3]>) {
+    let hw = style.width / 2.0;
+    let turn = cross(d0, d1);
+    if turn == 0.0 {
+        // The line either goes straight on or turns back on itself, which only a round join fills.
+        if dot(d0, d1) < 0.0 && style.join == Join::Round {
+            fan(p, hw, scale(perp(d0), -hw), PI, style.detail, tris);
+        }
+        return;
+    }
+    // The outside of a left turn is on the right.
+    let side = if turn > 0.0 { -hw } else { hw };
+    let (o0, o1) = (scale(perp(d0), side), scale(perp(d1), side));
+    let (a, b) = (add(p, o0), add(p, o1));
+
+    match style.join {
+        Join::Round => fan(p, hw, o0, angle_between(o0, o1), style.detail, tris),
+        Join::Miter => {
+            // The miter tip is on the bisector of the outer edges.
+            let bisector = normalize(add(o0, o1));
+            let cos_half = dot(bisector, normalize(o0));
+            if cos_half > 0.0 && 1.0 / cos_half <= style.miter_limit {
+                let tip = add(p, scale(bisector, hw / cos_half));
+                tris.push([p, a, tip]);
+                tris.push([p, tip, b]);
+            } else {
+                tris.push([p, a, b]);
+            }
+        }
+        Join::Bevel => tris.push([p, a, b]),
+    }
+}
+
+/// Draw the cap at the end point `p` of a line which leaves it in direction `d`.
+fn cap(p: [f32; 2], d: [f32; 2], style: &Stroke, tris: &mut Vec<[[f32; 2]; 3]>) {
+    let hw = style.width / 2.0;
+    let offset = scale(perp(d), hw);
+    match style.cap {
+        Cap::Butt => {}
+        Cap::Square => {
+            let out = scale(d, hw);
+            let (a, b) = (sub(p, offset), add(p, offset));
+            let (c, e) = (add(b, out), add(a, out));
+            tris.push([a, c, b]);
+            tris.push([a, e, c]);
+        }
+        Cap::Round => fan(p, hw, scale(offset, -1.0), PI, style.detail, tris),
+    }
+}
+
+/// Add a circular fan around `center` starting at `center + from` and turning `sweep` radians.
+pub(crate) fn fan(center: [f32; 2], radius: f32, from: [f32; 2], sweep: f32, detail: Detail, tris: &mut Vec<[[f32; 2]; 3]>) {
+    let segments = detail.segments(radius, sweep);
+    let start = from[1].atan2(from[0]);
+    let point = |i: usize| {
+        let angle = start + sweep * i as f32 / segments as f32;
+        [center[0] + radius * angle.cos(), center[1] + radius * angle.sin()]
+    };
+    for i in 0..segments {
+        tris.push([center, point(i), point(i + 1)]);
+    }
+}
+
+/// The signed angle to turn from `a` to `b`.
+#[inline]
+fn angle_between(a: [f32; 2], b: [f32; 2]) -> f32 {
+    cross(a, b).atan2(dot(a, b))
+}
+
+#[inline]
+fn add(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] + b[0], a[1] + b[1]]
+}
+
+#[inline]
+fn sub(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] - b[0], a[1] - b[1]]
+}
+
+#[inline]
+fn scale(a: [f32; 2], s: f32) -> [f32; 2] {
+    [a[0] * s, a[1] * s]
+}
+
+#[inline]
+fn dot(a: [f32; 2], b: [f32; 2]) -> f32 {
+    a[0] * b[0] + a[1] * b[1]
+}
+
+#[inline]
+fn cross(a: [f32; 2], b: [f32; 2]) -> f32 {
+    a[0] * b[1] - a[1] * b[0]
+}
+
+/// Rotate a vector a quarter turn counter-clockwise.
+#[inline]
+fn perp(a: [f32; 2]) -> [f32; 2] {
+    [-a[1], a[0]]
+}
+
+#[inline]
+fn normalize(a: [f32; 2]) -> [f32; 2] {
+    let len = dot(a, a).sqrt();
+    if len > 0.0 {
+        scale(a, 1.0 / len)
+    } else {
+        a
+    }
+}
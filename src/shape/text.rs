@@ -0,0 +1,230 @@
+use ::*;
+use std::vec;
+
+/// How the lines of a `Text` are aligned to its origin.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Align {
+    /// Lines start at the origin.
+    Left,
+    /// Lines are centered on the origin.
+    Center,
+    /// Lines end at the origin.
+    Right,
+}
+
+/// A string drawn with a `Font`.
+///
+/// Created by `text()`. The glyphs are white and textured with the font's atlas, so they can be
+/// recolored and transformed like any other shape.
+#[derive(Clone, Debug)]
+pub struct Text {
+    font: Font,
+    string: String,
+    size: f32,
+    align: Align,
+    wrap: Option<f32>,
+    line_spacing: f32,
+}
+
+impl Text {
+    /// Align every line to the origin with `align`. The default is `Align::Left`.
+    #[inline]
+    pub fn align(mut self, align: Align) -> Text {
+        self.align = align;
+        self
+    }
+
+    /// Break lines between words so that no line is wider than `width`. Words which are wider
+    /// than `width` get a line of their own.
+    #[inline]
+    pub fn wrap(mut self, width: f32) -> Text {
+        self.wrap = Some(width);
+        self
+    }
+
+    /// Multiply the distance between lines by `spacing`. The default is `1.0`, which is the
+    /// distance the font asks for.
+    #[inline]
+    pub fn line_spacing(mut self, spacing: f32) -> Text {
+        self.line_spacing = spacing;
+        self
+    }
+
+    /// Split the string into lines at line breaks and, if wrapping, between words.
+    fn lines(&self) -> Vec<String> {
+        let max = match self.wrap {
+            Some(width) => width * self.font.pixels() / self.size,
+            None => return self.string.lines().map(String::from).collect(),
+        };
+        let mut lines = Vec::new();
+        for paragraph in self.string.lines() {
+            let mut line = String::new();
+            for word in paragraph.split(' ') {
+                let candidate = if line.is_empty() {
+                    word.to_string()
+                } else {
+                    format!("{} {}", line, word)
+                };
+                if !line.is_empty() && self.font.pixel_width(&candidate) > max {
+                    lines.push(line);
+                    line = word.to_string();
+                } else {
+                    line = candidate;
+                }
+            }
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+impl IntoIterator for Text {
+    type IntoIter = vec::IntoIter<RendTri>;
+    type Item = RendTri;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let font = &self.font;
+        let line_height = font.line_height() * self.line_spacing;
+
+        // Rasterize every glyph before getting the atlas texture, which may grow meanwhile.
+        let mut glyphs = Vec::new();
+        for (n, line) in self.lines().iter().enumerate() {
+            let width = font.pixel_width(line);
+            let mut x = match self.align {
+                Align::Left => 0.0,
+                Align::Center => -width / 2.0,
+                Align::Right => -width,
+            };
+            let baseline = -(n as f32) * line_height;
+            let mut last = None;
+            for id in line.chars().filter_map(|c| font.glyph_id(c)) {
+                if let Some(last) = last {
+                    x += font.kerning(last, id);
+                }
+                last = Some(id);
+                if let Some(glyph) = font.atlas_glyph(id) {
+                    glyphs.push((x + glyph.offset[0] as f32, baseline - glyph.offset[1] as f32, glyph));
+                }
+                x += font.advance(id);
+            }
+        }
+        if glyphs.is_empty() {
+            return Vec::new().into_iter();
+        }
+
+        let texture = font.texture();
+        let (tw, th) = (texture.width() as f32, texture.height() as f32);
+        // Glyphs are laid out in pixels of the atlas, with y pointing up.
+        let unit = self.size / font.pixels();
+        let mut tris = Vec::with_capacity(glyphs.len() * 2);
+        for (left, top, glyph) in glyphs {
+            let (w, h) = (glyph.size[0] as f32, glyph.size[1] as f32);
+            let (x0, x1) = (left * unit, (left + w) * unit);
+            let (y0, y1) = ((top - h) * unit, top * unit);
+            let (u0, u1) = (glyph.min[0] as f32 / tw, (glyph.min[0] as f32 + w) / tw);
+            let (v0, v1) = (glyph.min[1] as f32 / th, (glyph.min[1] as f32 + h) / th);
+            tris.push(RendTri::from(Tri::new(
+                [[x0, y0], [x1, y0], [x0, y1]],
+                [[u0, v1], [u1, v1], [u0, v0]],
+                Color::WHITE,
+            )).map_texture(texture.clone()));
+            tris.push(RendTri::from(Tri::new(
+                [[x1, y1], [x0, y1], [x1, y0]],
+                [[u1, v0], [u0, v0], [u1, v1]],
+                Color::WHITE,
+            )).map_texture(texture.clone()));
+        }
+        tris.into_iter()
+    }
+}
+
+/// Takes a font, a string and the height of a line and creates the text. The first line starts
+/// at the origin on its baseline and following lines go downwards.
+///
+/// ## Example
+/// ```rust,no_run
+/// use nest::*;
+/// let mut app = Window::new("Example", 640, 480).unwrap();
+/// let font = Font::open("examples/DejaVuSansMono.ttf").unwrap();
+/// app.draw(text(&font, "Hello,\nworld!", 0.2).align(Align::Center).recolor(Color::YELLOW));
+/// ```
+#[inline]
+pub fn text<S: Into<String>>(font: &Font, string: S, size: f32) -> Text {
+    Text {
+        font: font.clone(),
+        string: string.into(),
+        size: size,
+        align: Align::Left,
+        wrap: None,
+        line_spacing: 1.0,
+    }
+}
+
+/// A string drawn with a `BitmapFont`.
+///
+/// Created by `bitmap_text()`. Every glyph is an `Image` of a cell of the font's texture.
+#[derive(Clone, Debug)]
+pub struct BitmapText {
+    font: BitmapFont,
+    string: String,
+    size: f32,
+    align: Align,
+    line_spacing: f32,
+}
+
+impl BitmapText {
+    /// Align every line to the origin with `align`. The default is `Align::Left`.
+    #[inline]
+    pub fn align(mut self, align: Align) -> BitmapText {
+        self.align = align;
+        self
+    }
+
+    /// Multiply the distance between lines by `spacing`. The default is `1.0`, which puts the
+    /// cells of the lines right below each other.
+    #[inline]
+    pub fn line_spacing(mut self, spacing: f32) -> BitmapText {
+        self.line_spacing = spacing;
+        self
+    }
+}
+
+impl IntoIterator for BitmapText {
+    type IntoIter = vec::IntoIter<RendTri>;
+    type Item = RendTri;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let advance = self.font.advance(self.size);
+        let mut tris = Vec::new();
+        for (n, line) in self.string.lines().enumerate() {
+            let width = line.chars().count() as f32 * advance;
+            let left = match self.align {
+                Align::Left => 0.0,
+                Align::Center => -width / 2.0,
+                Align::Right => -width,
+            };
+            let bottom = -(n as f32) * self.size * self.line_spacing;
+            for (i, c) in line.chars().enumerate() {
+                // Characters without a cell, like spaces which are not in the texture, are skipped.
+                if let Some(region) = self.font.region(c) {
+                    let x = left + i as f32 * advance;
+                    tris.extend(image_region([x, bottom], [x + advance, bottom + self.size], region));
+                }
+            }
+        }
+        tris.into_iter()
+    }
+}
+
+/// Takes a bitmap font, a string and the height of a line and creates the text. The first line
+/// starts at the origin with its bottom edge and following lines go downwards.
+#[inline]
+pub fn bitmap_text<S: Into<String>>(font: &BitmapFont, string: S, size: f32) -> BitmapText {
+    BitmapText {
+        font: font.clone(),
+        string: string.into(),
+        size: size,
+        align: Align::Left,
+        line_spacing: 1.0,
+    }
+}
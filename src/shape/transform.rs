@@ -0,0 +1,163 @@
+use {cgm, Anchor, RendTri, Shape, Transform};
+use super::anchor_point;
+
+/// `Transformed` represents a shape which has been moved, rotated, scaled, sheared or reflected by
+/// an affine `Transform`.
+///
+/// Calling the transforming combinators on it again folds them into the same `Transform`
+/// instead of wrapping the shape another time.
+#[derive(Copy, Clone, Debug)]
+pub struct Transformed<S> {
+    shape: S,
+    transform: Transform,
+}
+
+impl<S> Transformed<S> {
+    pub(crate) fn new(shape: S, transform: Transform) -> Self {
+        Transformed {
+            shape: shape,
+            transform: transform,
+        }
+    }
+
+    #[inline]
+    fn then(&self, transform: Transform) -> Self where S: Clone {
+        Transformed::new(self.shape.clone(), self.transform.then(transform))
+    }
+
+    /// See `Shape::translate()`.
+    #[inline]
+    pub fn translate<V: Into<cgm::Vector2<f32>>>(&self, vector: V) -> Self where S: Clone {
+        self.then(Transform::translation(vector))
+    }
+
+    /// See `Shape::rotate()`.
+    #[inline]
+    pub fn rotate(&self, angle: f32) -> Self where S: Clone {
+        self.then(Transform::rotation(angle))
+    }
+
+    /// See `Shape::scale_both()`.
+    #[inline]
+    pub fn scale_both<V: Into<cgm::Vector2<f32>>>(&self, scale: V) -> Self where S: Clone {
+        self.then(Transform::scaling(scale))
+    }
+
+    /// See `Shape::scale()`.
+    #[inline]
+    pub fn scale(&self, scale: f32) -> Self where S: Clone {
+        self.then(Transform::scaling([scale, scale]))
+    }
+
+    /// See `Shape::scale_x()`.
+    #[inline]
+    pub fn scale_x(&self, scale_x: f32) -> Self where S: Clone {
+        self.then(Transform::scaling([scale_x, 1.0]))
+    }
+
+    /// See `Shape::scale_y()`.
+    #[inline]
+    pub fn scale_y(&self, scale_y: f32) -> Self where S: Clone {
+        self.then(Transform::scaling([1.0, scale_y]))
+    }
+
+    /// See `Shape::rotate_about()`.
+    #[inline]
+    pub fn rotate_about<P: Into<cgm::Point2<f32>>>(&self, pivot: P, angle: f32) -> Self where S: Clone {
+        self.then(Transform::IDENTITY.rotate_about(pivot, angle))
+    }
+
+    /// See `Shape::scale_about()`.
+    #[inline]
+    pub fn scale_about<P: Into<cgm::Point2<f32>>>(&self, pivot: P, scale: f32) -> Self where S: Clone {
+        self.then(Transform::IDENTITY.scale_about(pivot, scale))
+    }
+
+    /// See `Shape::rotate_about_anchor()`.
+    #[inline]
+    pub fn rotate_about_anchor(&self, anchor: Anchor, angle: f32) -> Self where S: Shape + Clone {
+        self.rotate_about(anchor_point(self, anchor), angle)
+    }
+
+    /// See `Shape::scale_about_anchor()`.
+    #[inline]
+    pub fn scale_about_anchor(&self, anchor: Anchor, scale: f32) -> Self where S: Shape + Clone {
+        self.scale_about(anchor_point(self, anchor), scale)
+    }
+
+    /// See `Shape::transform()`.
+    #[inline]
+    pub fn transform(&self, transform: Transform) -> Self where S: Clone {
+        self.then(transform)
+    }
+
+    /// See `Shape::shear()`.
+    #[inline]
+    pub fn shear<V: Into<cgm::Vector2<f32>>>(&self, shear: V) -> Self where S: Clone {
+        self.then(Transform::shearing(shear))
+    }
+
+    /// See `Shape::skew_x()`.
+    #[inline]
+    pub fn skew_x(&self, angle: f32) -> Self where S: Clone {
+        self.then(Transform::IDENTITY.skew_x(angle))
+    }
+
+    /// See `Shape::skew_y()`.
+    #[inline]
+    pub fn skew_y(&self, angle: f32) -> Self where S: Clone {
+        self.then(Transform::IDENTITY.skew_y(angle))
+    }
+
+    /// See `Shape::reflect_x()`.
+    #[inline]
+    pub fn reflect_x(&self) -> Self where S: Clone {
+        self.then(Transform::IDENTITY.reflect_x())
+    }
+
+    /// See `Shape::reflect_y()`.
+    #[inline]
+    pub fn reflect_y(&self) -> Self where S: Clone {
+        self.then(Transform::IDENTITY.reflect_y())
+    }
+
+    /// See `Shape::reflect_across()`.
+    #[inline]
+    pub fn reflect_across<P: Into<cgm::Point2<f32>>>(&self, a: P, b: P) -> Self where S: Clone {
+        self.then(Transform::reflection(a, b))
+    }
+}
+
+impl<S> IntoIterator for Transformed<S>
+where
+    S: Shape,
+{
+    type Item = RendTri;
+    type IntoIter = TransformedIter<S::IntoIter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        TransformedIter {
+            iter: self.shape.into_iter(),
+            transform: self.transform,
+        }
+    }
+}
+
+/// Iterator which is produced by `Transformed`
+#[derive(Clone, Debug)]
+pub struct TransformedIter<I> {
+    iter: I,
+    transform: Transform,
+}
+
+impl<I> Iterator for TransformedIter<I>
+where
+    I: Iterator<Item = RendTri>,
+{
+    type Item = RendTri;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let transform = self.transform;
+        self.iter.next().map(|t| t.map_pos(|p| transform.apply(p)))
+    }
+}
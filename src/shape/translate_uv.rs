@@ -1,47 +1,52 @@
 use {cgm, RendTri, Shape};
 
-/// `Translate` represents a shape which has been translated.
+/// `TranslateUv` represents a shape whose texture coordinates have been translated.
 #[derive(Copy, Clone, Debug)]
-pub struct Translate<S> {
+pub struct TranslateUv<S> {
     shape: S,
     v: cgm::Vector2<f32>,
 }
 
-impl<S> Translate<S> {
+impl<S> TranslateUv<S> {
     pub(crate) fn new(shape: S, v: cgm::Vector2<f32>) -> Self {
-        Translate { shape: shape, v: v }
+        TranslateUv {
+            shape: shape,
+            v: v,
+        }
     }
 }
 
-impl<S> IntoIterator for Translate<S>
+impl<S> IntoIterator for TranslateUv<S>
 where
     S: Shape,
 {
     type Item = RendTri;
-    type IntoIter = TranslateIter<S::IntoIter>;
+    type IntoIter = TranslateUvIter<S::IntoIter>;
 
     fn into_iter(self) -> Self::IntoIter {
-        TranslateIter {
+        TranslateUvIter {
             iter: self.shape.into_iter(),
             v: self.v,
         }
     }
 }
 
-/// Iterator which is produced by `Translate`
+/// Iterator which is produced by `TranslateUv`
 #[derive(Clone, Debug)]
-pub struct TranslateIter<I> {
+pub struct TranslateUvIter<I> {
     iter: I,
     v: cgm::Vector2<f32>,
 }
 
-impl<I> Iterator for TranslateIter<I>
+impl<I> Iterator for TranslateUvIter<I>
 where
     I: Iterator<Item = RendTri>,
 {
     type Item = RendTri;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|t| t.map_pos(|p| p + self.v))
+        self.iter
+            .next()
+            .map(|t| t.map_tex(|p| p + self.v))
     }
 }
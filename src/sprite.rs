@@ -0,0 +1,145 @@
+use serde_json::{self, Value};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path;
+use *;
+
+/// A texture which is sliced into frames, like the frames of an animation or a set of tiles.
+///
+/// # Example
+/// ```rust,no_run
+/// use nest::*;
+/// let mut app = Window::new("Example", 640, 480).unwrap();
+/// let texture = app.load_texture("hero.png").unwrap();
+/// let sheet = SpriteSheet::open_json(texture, "hero.json").unwrap();
+/// app.draw(image_region([-0.2, -0.2], [0.2, 0.2], sheet.get("walk 0").unwrap().clone()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SpriteSheet {
+    frames: Vec<TextureRegion>,
+    names: HashMap<String, usize>,
+}
+
+impl SpriteSheet {
+    /// Slice a `texture` into a grid of frames which are `cell` pixels large. Frames are numbered
+    /// from left to right and then from top to bottom. Pixels at the right and bottom edges which
+    /// do not fill a whole cell are left out.
+    pub fn grid<T: Into<Texture>>(texture: T, cell: [u32; 2]) -> SpriteSheet {
+        let texture = texture.into();
+        let cell = [cell[0].max(1), cell[1].max(1)];
+        let (columns, rows) = (texture.width() / cell[0], texture.height() / cell[1]);
+        let mut frames = Vec::new();
+        for row in 0..rows {
+            for column in 0..columns {
+                frames.push(TextureRegion::from_pixels(
+                    texture.clone(),
+                    [column * cell[0], row * cell[1]],
+                    cell,
+                ));
+            }
+        }
+        SpriteSheet {
+            frames: frames,
+            names: HashMap::new(),
+        }
+    }
+
+    /// Slice a `texture` into the frames listed in `json`, which is in the JSON format written
+    /// by TexturePacker and Aseprite.
+    ///
+    /// Both the array and the hash variant are supported. Frames are named by their file name.
+    /// Frames listed in a hash are numbered in the order of their names, so use the array variant
+    /// if the order matters. Rotated and trimmed frames are not supported and are reported as an
+    /// error, like frames which do not fit in the texture.
+    pub fn from_json<T: Into<Texture>>(texture: T, json: &str) -> Result<SpriteSheet> {
+        let texture = texture.into();
+        let root: Value = serde_json::from_str(json)?;
+        let entries: Vec<(String, &Value)> = match root.get("frames") {
+            Some(&Value::Array(ref frames)) => frames
+                .iter()
+                .enumerate()
+                .map(|(i, f)| {
+                    let name = f.get("filename").and_then(Value::as_str).map(String::from);
+                    (name.unwrap_or_else(|| i.to_string()), f)
+                })
+                .collect(),
+            Some(&Value::Object(ref frames)) => {
+                let mut entries: Vec<_> = frames.iter().map(|(name, f)| (name.clone(), f)).collect();
+                entries.sort_by(|a, b| a.0.cmp(&b.0));
+                entries
+            }
+            _ => bail!(ErrorKind::InvalidSpriteSheet("no `frames` array or object".into())),
+        };
+
+        let mut sheet = SpriteSheet {
+            frames: Vec::new(),
+            names: HashMap::new(),
+        };
+        for (name, entry) in entries {
+            if entry.get("rotated").and_then(Value::as_bool) == Some(true) {
+                bail!(ErrorKind::InvalidSpriteSheet(format!("frame `{}` is rotated", name)));
+            }
+            if entry.get("trimmed").and_then(Value::as_bool) == Some(true) {
+                bail!(ErrorKind::InvalidSpriteSheet(format!("frame `{}` is trimmed", name)));
+            }
+            let rect = entry.get("frame");
+            let field = |key: &str| {
+                rect.and_then(|r| r.get(key))
+                    .and_then(Value::as_u64)
+                    .and_then(|v| if v <= u32::max_value() as u64 { Some(v as u32) } else { None })
+            };
+            let region = match (field("x"), field("y"), field("w"), field("h")) {
+                (Some(x), Some(y), Some(w), Some(h)) => {
+                    let fits = |start: u32, size: u32, limit: u32| start.checked_add(size).map_or(false, |end| end <= limit);
+                    if !fits(x, w, texture.width()) || !fits(y, h, texture.height()) {
+                        bail!(ErrorKind::InvalidSpriteSheet(format!("frame `{}` does not fit in the texture", name)));
+                    }
+                    TextureRegion::from_pixels(texture.clone(), [x, y], [w, h])
+                }
+                _ => bail!(ErrorKind::InvalidSpriteSheet(format!("frame `{}` has no valid `frame` rectangle", name))),
+            };
+            sheet.names.insert(name, sheet.frames.len());
+            sheet.frames.push(region);
+        }
+        Ok(sheet)
+    }
+
+    /// Slice a `texture` into the frames listed in the JSON file at `path`, see
+    /// `SpriteSheet::from_json()`.
+    pub fn open_json<T: Into<Texture>, P: AsRef<path::Path>>(texture: T, path: P) -> Result<SpriteSheet> {
+        let mut json = String::new();
+        File::open(path)?.read_to_string(&mut json)?;
+        SpriteSheet::from_json(texture, &json)
+    }
+
+    /// The number of frames.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns true if the sheet has no frames.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// The frame at `index`.
+    #[inline]
+    pub fn frame(&self, index: usize) -> Option<&TextureRegion> {
+        self.frames.get(index)
+    }
+
+    /// The frame called `name` in the JSON file the sheet was loaded from.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&TextureRegion> {
+        self.names.get(name).map(|&i| &self.frames[i])
+    }
+
+    /// All frames in order.
+    #[inline]
+    pub fn frames(&self) -> &[TextureRegion] {
+        &self.frames
+    }
+}
@@ -0,0 +1,1073 @@
+use std::f32::consts::PI;
+use std::fmt::Write as FmtWrite;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path;
+use std::rc::Rc;
+use std::vec;
+use xml::attribute::OwnedAttribute;
+use xml::reader::{EventReader, XmlEvent};
+use shape::Segment;
+use *;
+
+/// A shape loaded from an SVG document.
+///
+/// The supported elements are `svg`, `g`, `rect`, `circle`, `ellipse`, `polygon` and `path`, with
+/// the `transform`, `fill`, `fill-opacity`, `fill-rule`, `stroke`, `stroke-width`,
+/// `stroke-opacity` and `opacity` attributes, which may also be given in a `style` attribute.
+/// Group transforms are applied with `translate()`, `rotate()` and `scale_both()`.
+///
+/// Anything else in the document is skipped and reported in `Svg::warnings()`, except for the
+/// `id`, `version` and `baseProfile` attributes, which never change what is drawn.
+///
+/// The document's `viewBox`, or its `width` and `height` if there is none, is fit to the square
+/// from `(-1.0, -1.0)` to `(1.0, 1.0)` with the y axis pointing up. Like in a browser, the aspect
+/// ratio is kept and the drawing is centered unless `preserveAspectRatio` says otherwise. A
+/// document with an empty viewport draws nothing.
+///
+/// # Example
+/// ```rust
+/// use nest::*;
+///
+/// let icon = Svg::parse(r##"
+///     <svg viewBox="0 0 10 10">
+///         <rect x="1" y="1" width="8" height="8" fill="#ff0000" />
+///         <text x="5" y="5">Hi</text>
+///     </svg>
+/// "##).unwrap();
+/// assert_eq!(icon.warnings().len(), 1);
+/// assert_eq!(icon.into_iter().count(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Svg {
+    tris: Rc<Vec<RendTri>>,
+    warnings: Vec<String>,
+}
+
+impl Svg {
+    /// Load an SVG document from a file.
+    pub fn open<P: AsRef<path::Path>>(path: P) -> Result<Svg> {
+        let mut source = String::new();
+        File::open(path)?.read_to_string(&mut source)?;
+        Svg::parse(&source)
+    }
+
+    /// Load an SVG document from a string.
+    pub fn parse(source: &str) -> Result<Svg> {
+        let mut loader = Loader {
+            stack: Vec::new(),
+            skipping: 0,
+            viewport: None,
+            root: None,
+            warnings: Vec::new(),
+        };
+        for event in EventReader::from_str(source) {
+            match event? {
+                XmlEvent::StartElement { name, attributes, .. } => {
+                    loader.start(&name.local_name, &attributes)
+                }
+                XmlEvent::EndElement { .. } => loader.end(),
+                _ => {}
+            }
+        }
+        let (tris, viewport) = match (loader.root, loader.viewport) {
+            (Some(tris), Some(viewport)) => (tris, viewport),
+            _ => bail!(ErrorKind::InvalidSvg("document has no `svg` element".into())),
+        };
+
+        // Fit the view box to the window and flip the y axis, which points down in SVG.
+        let (x, y, width, height) = (viewport.rect[0], viewport.rect[1], viewport.rect[2], viewport.rect[3]);
+        let tris = if width > 0.0 && height > 0.0 {
+            let (scale_x, scale_y) = viewport.scale();
+            // The space left over on each axis, which is negative when the drawing is sliced.
+            let align = viewport.align.unwrap_or([0.5, 0.5]);
+            let left = -1.0 + (2.0 - width * scale_x) * align[0];
+            let top = 1.0 - (2.0 - height * scale_y) * align[1];
+            tris.translate([-x, -y])
+                .scale_both([scale_x, -scale_y])
+                .translate([left, top])
+                .into_iter()
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Ok(Svg {
+            tris: Rc::new(tris),
+            warnings: loader.warnings,
+        })
+    }
+
+    /// Descriptions of everything in the document which was not understood and was skipped.
+    #[inline]
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+}
+
+impl IntoIterator for Svg {
+    type IntoIter = vec::IntoIter<RendTri>;
+    type Item = RendTri;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        match Rc::try_unwrap(self.tris) {
+            Ok(tris) => tris.into_iter(),
+            Err(tris) => (*tris).clone().into_iter(),
+        }
+    }
+}
+
+/// How the view box of a document is fit to the window.
+#[derive(Copy, Clone, Debug)]
+struct Viewport {
+    /// The `x`, `y`, `width` and `height` of the view box.
+    rect: [f32; 4],
+    /// Where the view box is placed on each axis, from `0.0` for the start to `1.0` for the end,
+    /// or `None` if it is stretched to fit.
+    align: Option<[f32; 2]>,
+    /// Whether the view box covers the window instead of fitting inside of it.
+    slice: bool,
+}
+
+impl Viewport {
+    /// How much the view box is scaled on each axis to fit it to the window.
+    fn scale(&self) -> (f32, f32) {
+        let (width, height) = (self.rect[2], self.rect[3]);
+        match self.align {
+            Some(_) if self.slice => (2.0 / width.min(height), 2.0 / width.min(height)),
+            Some(_) => (2.0 / width.max(height), 2.0 / width.max(height)),
+            None => (2.0 / width, 2.0 / height),
+        }
+    }
+}
+
+/// A fill or stroke paint.
+#[derive(Copy, Clone, Debug)]
+enum Paint {
+    None,
+    Color(Color),
+}
+
+/// The inherited presentation attributes of an element.
+#[derive(Copy, Clone, Debug)]
+struct Style {
+    fill: Paint,
+    fill_opacity: f32,
+    fill_rule: FillRule,
+    stroke: Paint,
+    stroke_width: f32,
+    stroke_opacity: f32,
+    /// The product of the `opacity` of the element and all of its ancestors.
+    opacity: f32,
+}
+
+impl Default for Style {
+    fn default() -> Style {
+        Style {
+            fill: Paint::Color(Color::BLACK),
+            fill_opacity: 1.0,
+            fill_rule: FillRule::NonZero,
+            stroke: Paint::None,
+            stroke_width: 1.0,
+            stroke_opacity: 1.0,
+            opacity: 1.0,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Transform {
+    Translate(f32, f32),
+    Rotate(f32),
+    Scale(f32, f32),
+}
+
+/// An element which has been started but not ended.
+struct Element {
+    style: Style,
+    transforms: Vec<Transform>,
+    tris: Vec<RendTri>,
+}
+
+struct Loader {
+    stack: Vec<Element>,
+    /// The depth inside of an element which is being skipped.
+    skipping: usize,
+    viewport: Option<Viewport>,
+    root: Option<Vec<RendTri>>,
+    warnings: Vec<String>,
+}
+
+impl Loader {
+    fn warn<S: Into<String>>(&mut self, warning: S) {
+        self.warnings.push(warning.into());
+    }
+
+    fn start(&mut self, name: &str, attributes: &[OwnedAttribute]) {
+        if self.skipping > 0 {
+            self.skipping += 1;
+            return;
+        }
+        match name {
+            "svg" | "g" | "rect" | "circle" | "ellipse" | "polygon" | "path" => {}
+            // These never draw anything.
+            "title" | "desc" | "metadata" | "defs" => {
+                self.skipping = 1;
+                return;
+            }
+            _ => {
+                self.warn(format!("unsupported element `{}`", name));
+                self.skipping = 1;
+                return;
+            }
+        }
+        if name == "svg" && !self.stack.is_empty() {
+            self.warn("nested `svg` elements are not supported");
+            self.skipping = 1;
+            return;
+        }
+        if name != "svg" && self.stack.is_empty() {
+            self.warn(format!("`{}` element outside of the `svg` element", name));
+            self.skipping = 1;
+            return;
+        }
+
+        let mut style = self.stack.last().map(|e| e.style).unwrap_or_default();
+        // Opacity is not inherited, it multiplies with the opacity of the ancestors.
+        let parent_opacity = style.opacity;
+        style.opacity = 1.0;
+        let mut transforms = Vec::new();
+        let mut geometry = Vec::new();
+
+        let mut properties: Vec<(String, String)> = attributes
+            .iter()
+            .map(|a| (a.name.local_name.clone(), a.value.clone()))
+            .collect();
+        if let Some(pos) = properties.iter().position(|p| p.0 == "style") {
+            let (_, value) = properties.remove(pos);
+            properties.extend(value.split(';').filter_map(|declaration| {
+                let mut parts = declaration.splitn(2, ':');
+                match (parts.next(), parts.next()) {
+                    (Some(k), Some(v)) => Some((k.trim().to_string(), v.trim().to_string())),
+                    _ => None,
+                }
+            }));
+        }
+
+        for (key, value) in properties {
+            match &key[..] {
+                "fill" => style.fill = self.paint(&value, style.fill),
+                "stroke" => style.stroke = self.paint(&value, style.stroke),
+                "fill-opacity" => style.fill_opacity = self.number(&value),
+                "stroke-opacity" => style.stroke_opacity = self.number(&value),
+                "stroke-width" => style.stroke_width = self.length(&value),
+                "opacity" => style.opacity = self.number(&value),
+                "fill-rule" => match &value[..] {
+                    "nonzero" => style.fill_rule = FillRule::NonZero,
+                    "evenodd" => style.fill_rule = FillRule::EvenOdd,
+                    _ => self.warn(format!("unsupported fill-rule `{}`", value)),
+                },
+                "transform" => transforms = self.transform(&value),
+                "x" | "y" | "width" | "height" | "rx" | "ry" | "r" | "cx" | "cy" | "points" | "d" | "viewBox"
+                | "preserveAspectRatio" => geometry.push((key, value)),
+                "id" | "version" | "baseProfile" => {}
+                _ => {
+                    if !key.starts_with("xmlns") {
+                        self.warn(format!("unsupported attribute `{}` on `{}`", key, name));
+                    }
+                }
+            }
+        }
+        style.opacity *= parent_opacity;
+
+        let mut element = Element {
+            style: style,
+            transforms: transforms,
+            tris: Vec::new(),
+        };
+        let used: &[&str] = match name {
+            "svg" => &["viewBox", "width", "height", "preserveAspectRatio"],
+            "rect" => &["x", "y", "width", "height", "rx", "ry"],
+            "circle" => &["cx", "cy", "r"],
+            "ellipse" => &["cx", "cy", "rx", "ry"],
+            "polygon" => &["points"],
+            "path" => &["d"],
+            _ => &[],
+        };
+        for &(ref key, _) in &geometry {
+            if !used.contains(&&key[..]) {
+                self.warn(format!("unsupported attribute `{}` on `{}`", key, name));
+            }
+        }
+        {
+            let attribute = |key: &str| geometry.iter().find(|g| g.0 == key).map(|g| &g.1[..]);
+            let path = match name {
+                "svg" => {
+                    let viewport = match attribute("viewBox") {
+                        Some(view_box) => {
+                            let v = numbers(view_box);
+                            if v.len() == 4 { Some([v[0], v[1], v[2], v[3]]) } else { None }
+                        }
+                        None => match (attribute("width"), attribute("height")) {
+                            (Some(w), Some(h)) => Some([0.0, 0.0, self.length(w), self.length(h)]),
+                            _ => None,
+                        },
+                    };
+                    if viewport.is_none() {
+                        self.warn("`svg` has no usable viewBox, width or height");
+                    }
+                    let rect = viewport.unwrap_or([0.0, 0.0, 100.0, 100.0]);
+                    if !(rect[2] > 0.0 && rect[3] > 0.0) {
+                        self.warn("`svg` has an empty viewport, so nothing is drawn");
+                    }
+                    let (align, slice) = match attribute("preserveAspectRatio") {
+                        Some(value) => self.aspect_ratio(value),
+                        None => (Some([0.5, 0.5]), false),
+                    };
+                    self.viewport = Some(Viewport {
+                        rect: rect,
+                        align: align,
+                        slice: slice,
+                    });
+                    None
+                }
+                "g" => None,
+                "rect" => {
+                    let (x, y) = (self.length_or(attribute("x")), self.length_or(attribute("y")));
+                    let (w, h) = (self.length_or(attribute("width")), self.length_or(attribute("height")));
+                    let (rx, ry) = match (attribute("rx"), attribute("ry")) {
+                        (Some(rx), Some(ry)) => (self.length(rx), self.length(ry)),
+                        (Some(r), None) | (None, Some(r)) => {
+                            let r = self.length(r);
+                            (r, r)
+                        }
+                        (None, None) => (0.0, 0.0),
+                    };
+                    let (rx, ry) = (rx.min(w / 2.0), ry.min(h / 2.0));
+                    let path = if rx > 0.0 && ry > 0.0 {
+                        Path::new()
+                            .move_to([x + rx, y])
+                            .line_to([x + w - rx, y])
+                            .arc_to([rx, ry], 0.0, false, true, [x + w, y + ry])
+                            .line_to([x + w, y + h - ry])
+                            .arc_to([rx, ry], 0.0, false, true, [x + w - rx, y + h])
+                            .line_to([x + rx, y + h])
+                            .arc_to([rx, ry], 0.0, false, true, [x, y + h - ry])
+                            .line_to([x, y + ry])
+                            .arc_to([rx, ry], 0.0, false, true, [x + rx, y])
+                    } else {
+                        Path::new()
+                            .move_to([x, y])
+                            .line_to([x + w, y])
+                            .line_to([x + w, y + h])
+                            .line_to([x, y + h])
+                    };
+                    Some(path.close())
+                }
+                "circle" | "ellipse" => {
+                    let (cx, cy) = (self.length_or(attribute("cx")), self.length_or(attribute("cy")));
+                    let (rx, ry) = if name == "circle" {
+                        let r = self.length_or(attribute("r"));
+                        (r, r)
+                    } else {
+                        (self.length_or(attribute("rx")), self.length_or(attribute("ry")))
+                    };
+                    Some(Path::new()
+                        .move_to([cx + rx, cy])
+                        .arc_to([rx, ry], 0.0, false, true, [cx - rx, cy])
+                        .arc_to([rx, ry], 0.0, false, true, [cx + rx, cy])
+                        .close())
+                }
+                "polygon" => {
+                    let v = numbers(attribute("points").unwrap_or(""));
+                    let mut path = Path::new();
+                    for (i, p) in v.chunks(2).filter(|p| p.len() == 2).enumerate() {
+                        path = if i == 0 { path.move_to([p[0], p[1]]) } else { path.line_to([p[0], p[1]]) };
+                    }
+                    Some(path.close())
+                }
+                "path" => match path_data(attribute("d").unwrap_or("")) {
+                    Ok(path) => Some(path),
+                    Err((path, error)) => {
+                        self.warnings.push(error);
+                        Some(path)
+                    }
+                },
+                _ => unreachable!(),
+            };
+            if let Some(path) = path {
+                let tolerance = self.tolerance(&element.transforms);
+                element.tris = draw(&path.tolerance(tolerance), &element.style, tolerance);
+            }
+        }
+        self.stack.push(element);
+    }
+
+    fn end(&mut self) {
+        if self.skipping > 0 {
+            self.skipping -= 1;
+            return;
+        }
+        let element = match self.stack.pop() {
+            Some(element) => element,
+            None => return,
+        };
+        let mut tris = element.tris;
+        // The last transform in the list is the first one applied.
+        for transform in element.transforms.iter().rev() {
+            tris = match *transform {
+                Transform::Translate(x, y) => tris.translate([x, y]).into_iter().collect(),
+                Transform::Rotate(angle) => tris.rotate(angle).into_iter().collect(),
+                Transform::Scale(x, y) => tris.scale_both([x, y]).into_iter().collect(),
+            };
+        }
+        match self.stack.last_mut() {
+            Some(parent) => parent.tris.extend(tris),
+            None => self.root = Some(tris),
+        }
+    }
+
+    /// The tolerance for flattening curves in the units of an element with `transforms`, so that
+    /// they stay within the default tolerance once they are scaled to the window.
+    fn tolerance(&self, transforms: &[Transform]) -> f32 {
+        let mut scale = self.viewport.map_or(1.0, |v| {
+            let (x, y) = v.scale();
+            x.max(y)
+        });
+        let ancestors = self.stack.iter().flat_map(|e| e.transforms.iter());
+        for transform in ancestors.chain(transforms) {
+            if let Transform::Scale(x, y) = *transform {
+                scale *= x.abs().max(y.abs());
+            }
+        }
+        if scale > 0.0 && scale.is_finite() {
+            DEFAULT_TOLERANCE / scale
+        } else {
+            DEFAULT_TOLERANCE
+        }
+    }
+
+    /// Parse a `preserveAspectRatio` attribute into the alignment and slicing of a `Viewport`.
+    fn aspect_ratio(&mut self, value: &str) -> (Option<[f32; 2]>, bool) {
+        let mut parts = value.split_whitespace();
+        let align = match parts.next().unwrap_or("xMidYMid") {
+            "none" => None,
+            align if align.len() == 8 && align.is_ascii() && align.starts_with('x') && align[4..].starts_with('Y') => {
+                let position = |s: &str| match s {
+                    "Min" => Some(0.0),
+                    "Mid" => Some(0.5),
+                    "Max" => Some(1.0),
+                    _ => None,
+                };
+                match (position(&align[1..4]), position(&align[5..8])) {
+                    (Some(x), Some(y)) => Some([x, y]),
+                    _ => {
+                        self.warn(format!("invalid preserveAspectRatio `{}`", value));
+                        Some([0.5, 0.5])
+                    }
+                }
+            }
+            _ => {
+                self.warn(format!("invalid preserveAspectRatio `{}`", value));
+                Some([0.5, 0.5])
+            }
+        };
+        let slice = match parts.next() {
+            None | Some("meet") => false,
+            Some("slice") => true,
+            Some(other) => {
+                self.warn(format!("invalid preserveAspectRatio `{}`", other));
+                false
+            }
+        };
+        (align, slice)
+    }
+
+    fn paint(&mut self, value: &str, inherited: Paint) -> Paint {
+        let value = value.trim();
+        match value {
+            "none" => return Paint::None,
+            "inherit" => return inherited,
+            _ => {}
+        }
+        match color(value) {
+            Some(c) => Paint::Color(c),
+            None => {
+                self.warn(format!("unsupported paint `{}`", value));
+                Paint::None
+            }
+        }
+    }
+
+    fn number(&mut self, value: &str) -> f32 {
+        match value.trim().parse() {
+            Ok(n) => n,
+            Err(_) => {
+                self.warn(format!("invalid number `{}`", value));
+                0.0
+            }
+        }
+    }
+
+    fn length(&mut self, value: &str) -> f32 {
+        let value = value.trim();
+        let number = value.trim_end_matches("px");
+        if number.len() != value.len() || number.parse::<f32>().is_ok() {
+            self.number(number)
+        } else {
+            let end = number
+                .find(|c: char| c.is_alphabetic() || c == '%')
+                .unwrap_or(number.len());
+            self.warn(format!("unsupported length unit in `{}`", value));
+            self.number(&number[..end])
+        }
+    }
+
+    fn length_or(&mut self, value: Option<&str>) -> f32 {
+        value.map(|v| self.length(v)).unwrap_or(0.0)
+    }
+
+    fn transform(&mut self, value: &str) -> Vec<Transform> {
+        let mut transforms = Vec::new();
+        for item in value.split(')') {
+            let mut parts = item.splitn(2, '(');
+            let (name, args) = match (parts.next(), parts.next()) {
+                (Some(name), Some(args)) => (name.trim().trim_start_matches(','), numbers(args)),
+                _ => continue,
+            };
+            let name = name.trim();
+            let arg = |i: usize, default: f32| args.get(i).cloned().unwrap_or(default);
+            match name {
+                "translate" => transforms.push(Transform::Translate(arg(0, 0.0), arg(1, 0.0))),
+                "scale" => transforms.push(Transform::Scale(arg(0, 1.0), arg(1, arg(0, 1.0)))),
+                "rotate" => {
+                    let (cx, cy) = (arg(1, 0.0), arg(2, 0.0));
+                    transforms.push(Transform::Translate(cx, cy));
+                    transforms.push(Transform::Rotate(arg(0, 0.0) * PI / 180.0));
+                    transforms.push(Transform::Translate(-cx, -cy));
+                }
+                "matrix" if args.len() == 6 => {
+                    // Split the matrix into a translation, rotation and scale if it has no skew.
+                    let (a, b, c, d) = (args[0], args[1], args[2], args[3]);
+                    let sx = (a * a + b * b).sqrt();
+                    let sy = (a * d - b * c) / sx;
+                    if !(sx > 0.0) || (a * c + b * d).abs() > 1e-4 * sx * sx {
+                        self.warn(format!("unsupported skewed transform `{})`", item.trim()));
+                        continue;
+                    }
+                    transforms.push(Transform::Translate(args[4], args[5]));
+                    transforms.push(Transform::Rotate(b.atan2(a)));
+                    transforms.push(Transform::Scale(sx, sy));
+                }
+                _ => self.warn(format!("unsupported transform `{})`", item.trim())),
+            }
+        }
+        transforms
+    }
+}
+
+/// Triangulate the fill and stroke of an element, flattening round joins within `tolerance`.
+fn draw(path: &Path, style: &Style, tolerance: f32) -> Vec<RendTri> {
+    let mut tris = Vec::new();
+    if let Paint::Color(color) = style.fill {
+        let color = color.alpha(style.fill_opacity * style.opacity);
+        tris.extend(path.fill(style.fill_rule).recolor(color));
+    }
+    if let Paint::Color(color) = style.stroke {
+        let color = color.alpha(style.stroke_opacity * style.opacity);
+        let mut stroke = Stroke::new(style.stroke_width);
+        stroke.detail = Detail::Tolerance(tolerance);
+        tris.extend(path.stroke(stroke).recolor(color));
+    }
+    tris
+}
+
+/// Parse a color in one of the forms `#rgb`, `#rrggbb`, `rgb(r, g, b)` or a basic color name.
+fn color(value: &str) -> Option<Color> {
+    if value.starts_with('#') {
+        let hex = &value[1..];
+        if !hex.is_ascii() {
+            return None;
+        }
+        let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok();
+        return match hex.len() {
+            3 => match (digit(0), digit(1), digit(2)) {
+                (Some(r), Some(g), Some(b)) => Some([r * 17, g * 17, b * 17, 255].into()),
+                _ => None,
+            },
+            6 => match u32::from_str_radix(hex, 16) {
+                Ok(v) => Some([(v >> 16) as u8, (v >> 8) as u8, v as u8, 255].into()),
+                Err(_) => None,
+            },
+            _ => None,
+        };
+    }
+    if value.starts_with("rgb(") && value.ends_with(')') {
+        let channels = value[4..value.len() - 1]
+            .split(',')
+            .map(|c| {
+                let c = c.trim();
+                if c.ends_with('%') {
+                    c[..c.len() - 1].trim().parse::<f32>().ok().map(|v| v / 100.0)
+                } else {
+                    c.parse::<f32>().ok().map(|v| v / 255.0)
+                }
+            })
+            .collect::<Option<Vec<f32>>>();
+        return match channels {
+            Some(ref c) if c.len() == 3 => Some(Color([c[0], c[1], c[2], 1.0])),
+            _ => None,
+        };
+    }
+    match value {
+        "black" => Some(Color::BLACK),
+        "white" => Some(Color::WHITE),
+        "red" => Some(Color::RED),
+        "lime" => Some(Color::GREEN),
+        "green" => Some([0, 128, 0, 255].into()),
+        "blue" => Some(Color::BLUE),
+        "yellow" => Some(Color::YELLOW),
+        "cyan" | "aqua" => Some(Color::CYAN),
+        "magenta" | "fuchsia" => Some(Color::MAGENTA),
+        "gray" | "grey" => Some([128, 128, 128, 255].into()),
+        "silver" => Some([192, 192, 192, 255].into()),
+        "maroon" => Some([128, 0, 0, 255].into()),
+        "olive" => Some([128, 128, 0, 255].into()),
+        "navy" => Some([0, 0, 128, 255].into()),
+        "purple" => Some([128, 0, 128, 255].into()),
+        "teal" => Some([0, 128, 128, 255].into()),
+        "orange" => Some([255, 165, 0, 255].into()),
+        _ => None,
+    }
+}
+
+/// Split a list of numbers separated by whitespace and commas.
+fn numbers(value: &str) -> Vec<f32> {
+    let mut scanner = Scanner::new(value);
+    let mut numbers = Vec::new();
+    while let Some(n) = scanner.number() {
+        numbers.push(n);
+    }
+    numbers
+}
+
+/// Reads numbers, flags and commands from SVG path data and number lists.
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(source: &'a str) -> Scanner<'a> {
+        Scanner {
+            bytes: source.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        while self.pos < self.bytes.len() {
+            match self.bytes[self.pos] {
+                b' ' | b'\t' | b'\n' | b'\r' | b',' => self.pos += 1,
+                _ => break,
+            }
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_separators();
+        self.bytes.get(self.pos).cloned()
+    }
+
+    /// Read a command letter.
+    fn command(&mut self) -> Option<u8> {
+        match self.peek() {
+            Some(c) if (c as char).is_ascii_alphabetic() && c != b'e' && c != b'E' => {
+                self.pos += 1;
+                Some(c)
+            }
+            _ => None,
+        }
+    }
+
+    /// Read a number such as `-1.5e3`. Numbers need no separator if they can't be confused,
+    /// as in `1-2` or `0.5.5`.
+    fn number(&mut self) -> Option<f32> {
+        self.skip_separators();
+        let start = self.pos;
+        let b = self.bytes;
+        let mut i = self.pos;
+        if i < b.len() && (b[i] == b'+' || b[i] == b'-') {
+            i += 1;
+        }
+        let digits = |i: &mut usize| {
+            let s = *i;
+            while *i < b.len() && b[*i].is_ascii_digit() {
+                *i += 1;
+            }
+            *i > s
+        };
+        let mut any = digits(&mut i);
+        if i < b.len() && b[i] == b'.' {
+            i += 1;
+            any |= digits(&mut i);
+        }
+        if !any {
+            return None;
+        }
+        if i < b.len() && (b[i] == b'e' || b[i] == b'E') {
+            let mut j = i + 1;
+            if j < b.len() && (b[j] == b'+' || b[j] == b'-') {
+                j += 1;
+            }
+            if digits(&mut j) {
+                i = j;
+            }
+        }
+        self.pos = i;
+        ::std::str::from_utf8(&b[start..i]).ok().and_then(|s| s.parse().ok())
+    }
+
+    /// Read an arc flag, which is a single `0` or `1`.
+    fn flag(&mut self) -> Option<bool> {
+        match self.peek() {
+            Some(b'0') => {
+                self.pos += 1;
+                Some(false)
+            }
+            Some(b'1') => {
+                self.pos += 1;
+                Some(true)
+            }
+            _ => None,
+        }
+    }
+
+    fn point(&mut self) -> Option<[f32; 2]> {
+        match (self.number(), self.number()) {
+            (Some(x), Some(y)) => Some([x, y]),
+            _ => None,
+        }
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.peek().is_none()
+    }
+}
+
+/// Parse SVG path data. On an error, the path up to the error is returned with a description.
+fn path_data(d: &str) -> ::std::result::Result<Path, (Path, String)> {
+    let mut s = Scanner::new(d);
+    let mut path = Path::new();
+    let mut current = [0.0f32, 0.0];
+    let mut start = [0.0f32, 0.0];
+    // The last control point of the previous curve, for the smooth curve commands.
+    let mut last_cubic: Option<[f32; 2]> = None;
+    let mut last_quad: Option<[f32; 2]> = None;
+    let mut command = None;
+
+    while !s.at_end() {
+        let cmd = match s.command() {
+            Some(c) => c,
+            // A command may be repeated by just giving more arguments.
+            None => match command {
+                Some(b'M') => b'L',
+                Some(b'm') => b'l',
+                Some(b'Z') | Some(b'z') | None => {
+                    return Err((path, format!("invalid path data at `{}`", &d[s.pos..])))
+                }
+                Some(c) => c,
+            },
+        };
+        command = Some(cmd);
+        let relative = (cmd as char).is_ascii_lowercase();
+        let base = if relative { current } else { [0.0, 0.0] };
+        let offset = |p: [f32; 2]| [p[0] + base[0], p[1] + base[1]];
+        let reflect = |c: Option<[f32; 2]>| match c {
+            Some(c) => [2.0 * current[0] - c[0], 2.0 * current[1] - c[1]],
+            None => current,
+        };
+
+        let segment = match cmd.to_ascii_uppercase() {
+            b'M' => s.point().map(|p| Segment::Move(offset(p))),
+            b'L' => s.point().map(|p| Segment::Line(offset(p))),
+            b'H' => s.number().map(|x| Segment::Line([x + base[0], current[1]])),
+            b'V' => s.number().map(|y| Segment::Line([current[0], y + base[1]])),
+            b'C' => match (s.point(), s.point(), s.point()) {
+                (Some(c1), Some(c2), Some(p)) => Some(Segment::Cubic(offset(c1), offset(c2), offset(p))),
+                _ => None,
+            },
+            b'S' => match (s.point(), s.point()) {
+                (Some(c2), Some(p)) => Some(Segment::Cubic(reflect(last_cubic), offset(c2), offset(p))),
+                _ => None,
+            },
+            b'Q' => match (s.point(), s.point()) {
+                (Some(c), Some(p)) => Some(Segment::Quad(offset(c), offset(p))),
+                _ => None,
+            },
+            b'T' => s.point().map(|p| Segment::Quad(reflect(last_quad), offset(p))),
+            b'A' => match (s.number(), s.number(), s.number(), s.flag(), s.flag(), s.point()) {
+                (Some(rx), Some(ry), Some(rotation), Some(large_arc), Some(sweep), Some(p)) => {
+                    Some(Segment::Arc {
+                        radii: [rx, ry],
+                        rotation: rotation * PI / 180.0,
+                        large_arc: large_arc,
+                        sweep: sweep,
+                        to: offset(p),
+                    })
+                }
+                _ => None,
+            },
+            b'Z' => Some(Segment::Close),
+            _ => None,
+        };
+        let segment = match segment {
+            Some(segment) => segment,
+            None => return Err((path, format!("invalid path data at `{}`", &d[s.pos..]))),
+        };
+
+        last_cubic = None;
+        last_quad = None;
+        match segment {
+            Segment::Move(to) => {
+                current = to;
+                start = to;
+            }
+            Segment::Line(to) | Segment::Arc { to, .. } => current = to,
+            Segment::Cubic(_, c2, to) => {
+                last_cubic = Some(c2);
+                current = to;
+            }
+            Segment::Quad(c, to) => {
+                last_quad = Some(c);
+                current = to;
+            }
+            Segment::Close => current = start,
+        }
+        path.push(segment);
+    }
+    Ok(path)
+}
+
+/// Describe a `shape` as an SVG document which is `width` by `height` pixels.
+///
+/// Every triangle becomes a `polygon` filled with its color, with its alpha as opacity. Textures
+/// are embedded once as PNG images and textured triangles are filled with a pattern which maps
+/// the texture onto them, repeating it like the renderer does whatever the wrap mode of the
+/// sampler is. Triangles which map the texture the same way share a pattern. The color of
+/// textured triangles is multiplied with the texture by a `feColorMatrix` filter.
+///
+/// Coordinates are written as they are in the shape, so documents of similar scenes are easy to
+/// diff. The y axis is flipped by a group around all of the triangles.
+///
+/// # Example
+/// ```rust
+/// use nest::*;
+///
+/// let document = svg_string(rect([-0.5, -0.5], [0.5, 0.5]).recolor(Color::RED), 64, 64).unwrap();
+/// assert!(document.contains("<polygon"));
+/// assert!(document.contains("fill=\"#ff0000\""));
+/// ```
+pub fn svg_string<S: Shape>(shape: S, width: u32, height: u32) -> Result<String> {
+    let mut textures: Vec<(Texture, Sampler)> = Vec::new();
+    let mut fills: Vec<(usize, [f32; 6])> = Vec::new();
+    let mut tints: Vec<[f32; 3]> = Vec::new();
+    let mut defs = String::new();
+    let mut body = String::new();
+
+    for rend in shape {
+        let p = rend.tri.positions.0;
+        if !(edge(p[0], p[1], p[2]).abs() > 0.0) {
+            continue;
+        }
+        let color = Color(rend.tri.color);
+        let points = format!(
+            "{},{} {},{} {},{}",
+            p[0][0], p[0][1], p[1][0], p[1][1], p[2][0], p[2][1]
+        );
+        let mut attributes = if color.0[3] < 1.0 {
+            format!(" opacity=\"{}\"", color.0[3].max(0.0))
+        } else {
+            String::new()
+        };
+
+        let fill = match rend.texture {
+            Some(texture) => {
+                let sampler = rend.sampler;
+                let index = match textures.iter().position(|t| t.0.ptr_eq(&texture) && t.1 == sampler) {
+                    Some(index) => index,
+                    None => {
+                        let (w, h) = (texture.width(), texture.height());
+                        let mut png = Vec::new();
+                        img::png::PNGEncoder::new(&mut png).encode(
+                            &texture.image(),
+                            w,
+                            h,
+                            img::ColorType::RGBA(8),
+                        )?;
+                        let rendering = match sampler.magnify {
+                            Filter::Nearest => " image-rendering=\"optimizeSpeed\"",
+                            Filter::Linear => "",
+                        };
+                        let _ = write!(
+                            defs,
+                            "    <pattern id=\"texture{}\" patternUnits=\"userSpaceOnUse\" width=\"{}\" height=\"{}\">\n      \
+                             <image width=\"{}\" height=\"{}\"{} xlink:href=\"data:image/png;base64,{}\" />\n    </pattern>\n",
+                            textures.len(),
+                            w,
+                            h,
+                            w,
+                            h,
+                            rendering,
+                            base64(&png)
+                        );
+                        textures.push((texture.clone(), sampler));
+                        textures.len() - 1
+                    }
+                };
+                let (w, h) = (texture.width() as f32, texture.height() as f32);
+                let t = rend.tri.texcoords.0;
+                let t = [
+                    [t[0][0] * w, t[0][1] * h],
+                    [t[1][0] * w, t[1][1] * h],
+                    [t[2][0] * w, t[2][1] * h],
+                ];
+                match affine(t, p) {
+                    Some(m) => {
+                        let tint = [color.0[0], color.0[1], color.0[2]];
+                        if tint != [1.0; 3] {
+                            let filter = match tints.iter().position(|&c| c == tint) {
+                                Some(filter) => filter,
+                                None => {
+                                    let _ = write!(
+                                        defs,
+                                        "    <filter id=\"tint{}\" color-interpolation-filters=\"sRGB\">\n      \
+                                         <feColorMatrix type=\"matrix\" values=\"{} 0 0 0 0 0 {} 0 0 0 0 0 {} 0 0 0 0 0 1 0\" />\n    </filter>\n",
+                                        tints.len(),
+                                        tint[0],
+                                        tint[1],
+                                        tint[2]
+                                    );
+                                    tints.push(tint);
+                                    tints.len() - 1
+                                }
+                            };
+                            let _ = write!(attributes, " filter=\"url(#tint{})\"", filter);
+                        }
+                        let fill = match fills.iter().position(|&f| f == (index, m)) {
+                            Some(fill) => fill,
+                            None => {
+                                let _ = writeln!(
+                                    defs,
+                                    "    <pattern id=\"fill{}\" xlink:href=\"#texture{}\" patternTransform=\"matrix({} {} {} {} {} {})\" />",
+                                    fills.len(), index, m[0], m[1], m[2], m[3], m[4], m[5]
+                                );
+                                fills.push((index, m));
+                                fills.len() - 1
+                            }
+                        };
+                        format!("url(#fill{})", fill)
+                    }
+                    // All texture coordinates are on a line, so the triangle has a single color.
+                    None => {
+                        let texel = sample_texture(&texture, t[0]).0;
+                        hex(Color([texel[0] * color.0[0], texel[1] * color.0[1], texel[2] * color.0[2], 1.0]))
+                    }
+                }
+            }
+            None => hex(color),
+        };
+        let _ = writeln!(body, "    <polygon points=\"{}\" fill=\"{}\"{} />", points, fill, attributes);
+    }
+
+    let mut document = String::new();
+    let _ = writeln!(
+        document,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" \
+         width=\"{}\" height=\"{}\" viewBox=\"-1 -1 2 2\" preserveAspectRatio=\"none\">",
+        width, height
+    );
+    if !defs.is_empty() {
+        let _ = write!(document, "  <defs>\n{}  </defs>\n", defs);
+    }
+    let _ = write!(document, "  <g transform=\"scale(1 -1)\">\n{}  </g>\n</svg>\n", body);
+    Ok(document)
+}
+
+/// Describe a `shape` as an SVG document like `svg_string()` and save it to the file at `path`.
+///
+/// # Example
+/// ```rust,no_run
+/// use nest::*;
+///
+/// render_to_svg(circle([0.0, 0.0], 0.5), 256, 256, "circle.svg").unwrap();
+/// ```
+pub fn render_to_svg<S, P>(shape: S, width: u32, height: u32, path: P) -> Result<()>
+where
+    S: Shape,
+    P: AsRef<path::Path>,
+{
+    let document = svg_string(shape, width, height)?;
+    File::create(path)?.write_all(document.as_bytes())?;
+    Ok(())
+}
+
+/// Twice the signed area of the triangle `abc`.
+#[inline]
+fn edge(a: [f32; 2], b: [f32; 2], c: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Find the SVG matrix `[a, b, c, d, e, f]` of the affine transformation which moves each of the
+/// points `from` to the matching point in `to`.
+fn affine(from: [[f32; 2]; 3], to: [[f32; 2]; 3]) -> Option<[f32; 6]> {
+    let (s1, s2) = ([from[1][0] - from[0][0], from[1][1] - from[0][1]], [from[2][0] - from[0][0], from[2][1] - from[0][1]]);
+    let (d1, d2) = ([to[1][0] - to[0][0], to[1][1] - to[0][1]], [to[2][0] - to[0][0], to[2][1] - to[0][1]]);
+    let det = s1[0] * s2[1] - s2[0] * s1[1];
+    if !(det.abs() > 0.0) {
+        return None;
+    }
+    // The linear part is `[d1 d2] * inverse([s1 s2])`.
+    let a = (d1[0] * s2[1] - d2[0] * s1[1]) / det;
+    let c = (d2[0] * s1[0] - d1[0] * s2[0]) / det;
+    let b = (d1[1] * s2[1] - d2[1] * s1[1]) / det;
+    let d = (d2[1] * s1[0] - d1[1] * s2[0]) / det;
+    let e = to[0][0] - a * from[0][0] - c * from[0][1];
+    let f = to[0][1] - b * from[0][0] - d * from[0][1];
+    Some([a, b, c, d, e, f])
+}
+
+/// The color of the texel at `pixel`, repeating the texture.
+fn sample_texture(texture: &Texture, pixel: [f32; 2]) -> Color {
+    let (w, h) = (texture.width() as i64, texture.height() as i64);
+    if w == 0 || h == 0 {
+        return Color([0.0; 4]);
+    }
+    let x = ((pixel[0].floor() as i64 % w) + w) % w;
+    let y = ((pixel[1].floor() as i64 % h) + h) % h;
+    texture.image().get_pixel(x as u32, y as u32).data.into()
+}
+
+/// Format the color channels as `#rrggbb`.
+fn hex(color: Color) -> String {
+    let byte = |v: f32| (v.max(0.0).min(1.0) * 255.0).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", byte(color.0[0]), byte(color.0[1]), byte(color.0[2]))
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let n = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
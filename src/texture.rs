@@ -0,0 +1,459 @@
+use glium;
+use glium::texture::Texture2d;
+use std::cell::{Cell, Ref, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path;
+use std::rc::{Rc, Weak};
+use *;
+
+thread_local! {
+    /// The textures which were created from GPU textures, by the address of the GPU texture, so
+    /// that converting the same GPU texture again gives the same `Texture`.
+    static GPU_TEXTURES: RefCell<HashMap<usize, Weak<TextureData>>> = RefCell::new(HashMap::new());
+}
+
+/// An image which can be drawn by `image()` and friends.
+///
+/// The pixels are kept in memory so that the texture can be drawn by any backend, including the
+/// software `Canvas`. The first time a texture is drawn to a `Window` it is uploaded to the GPU.
+/// Cloning a `Texture` is cheap and clones share the same pixels, so changes made with
+/// `Texture::update()` show up in all of them.
+///
+/// A texture which is already on the GPU, like the ones returned by `Window::load_image()`, can
+/// be converted into a `Texture` too. Its pixels are only read back from the GPU if they are
+/// needed, such as for drawing it on a `Canvas`. Converting the same GPU texture again gives a
+/// clone of the same `Texture`, so shapes using either one are batched together.
+#[derive(Clone)]
+pub struct Texture(Rc<TextureData>);
+
+struct TextureData {
+    /// The pixels, or `None` if the texture was created on the GPU and has not been read back.
+    image: RefCell<Option<img::RgbaImage>>,
+    gpu: RefCell<Option<Rc<Texture2d>>>,
+    /// The part of the image which has changed since it was uploaded, as `[x, y, width, height]`.
+    dirty: Cell<Option<[u32; 4]>>,
+}
+
+impl Texture {
+    /// Create a texture from an RGBA image.
+    pub fn from_image(image: img::RgbaImage) -> Texture {
+        Texture(Rc::new(TextureData {
+            image: RefCell::new(Some(image)),
+            gpu: RefCell::new(None),
+            dirty: Cell::new(None),
+        }))
+    }
+
+    /// Create a texture from the contents of an image file, such as PNG or JPEG data included
+    /// with `include_bytes!`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use nest::*;
+    /// let petal = Texture::from_bytes(include_bytes!("../examples/petal.png")).unwrap();
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Texture> {
+        Ok(Texture::from_image(img::load_from_memory(bytes)?.to_rgba()))
+    }
+
+    /// Create a texture which is `width` by `height` pixels from RGBA `pixels` with 8 bits per
+    /// channel, starting with the top row.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use nest::*;
+    /// // A red and a green pixel.
+    /// let texture = Texture::from_rgba(2, 1, vec![255, 0, 0, 255, 0, 255, 0, 255]).unwrap();
+    /// assert_eq!(texture.width(), 2);
+    /// ```
+    pub fn from_rgba(width: u32, height: u32, pixels: Vec<u8>) -> Result<Texture> {
+        match img::RgbaImage::from_raw(width, height, pixels) {
+            Some(image) => Ok(Texture::from_image(image)),
+            None => bail!(ErrorKind::InvalidPixels("the buffer is too small for the size")),
+        }
+    }
+
+    /// Load a texture from an image file. This does not require a `Window`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use nest::*;
+    /// let pic = Texture::open("res/city.jpg").unwrap();
+    /// ```
+    pub fn open<P: AsRef<path::Path>>(path: P) -> Result<Texture> {
+        let mut buf = Vec::new();
+        File::open(path)?.read_to_end(&mut buf)?;
+        Texture::from_bytes(&buf)
+    }
+
+    /// The width of the texture in pixels.
+    #[inline]
+    pub fn width(&self) -> u32 {
+        match *self.0.image.borrow() {
+            Some(ref image) => image.width(),
+            None => self.0.gpu.borrow().as_ref().map_or(0, |t| t.width()),
+        }
+    }
+
+    /// The height of the texture in pixels.
+    #[inline]
+    pub fn height(&self) -> u32 {
+        match *self.0.image.borrow() {
+            Some(ref image) => image.height(),
+            None => self.0.gpu.borrow().as_ref().map_or(0, |t| t.height()),
+        }
+    }
+
+    /// The pixels of the texture.
+    #[inline]
+    pub fn image(&self) -> Ref<'_, img::RgbaImage> {
+        self.read_back();
+        Ref::map(self.0.image.borrow(), |image| image.as_ref().unwrap())
+    }
+
+    /// Replace the pixels of the texture starting at `position` with `image`. The change is
+    /// uploaded to the GPU the next time the texture is drawn, so this can be done every frame
+    /// for generated or video content. If the texture has mipmaps they are regenerated after the
+    /// upload. An error is returned if the image doesn't fit inside the texture at `position`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use nest::*;
+    /// let texture = Texture::from_image(RgbaImage::new(4, 4));
+    /// let white = RgbaImage::from_raw(2, 2, vec![255; 16]).unwrap();
+    /// texture.update([1, 1], &white).unwrap();
+    /// assert_eq!(texture.image().get_pixel(2, 2).data, [255, 255, 255, 255]);
+    /// ```
+    pub fn update(&self, position: [u32; 2], image: &img::RgbaImage) -> Result<()> {
+        let (w, h) = image.dimensions();
+        {
+            self.read_back();
+            let mut target = self.0.image.borrow_mut();
+            let target = target.as_mut().unwrap();
+            let fits = |start: u32, size: u32, limit: u32| start.checked_add(size).map_or(false, |end| end <= limit);
+            if !fits(position[0], w, target.width()) || !fits(position[1], h, target.height()) {
+                bail!(ErrorKind::InvalidPixels("the update reaches outside of the texture"));
+            }
+            for (x, y, pixel) in image.enumerate_pixels() {
+                target.put_pixel(position[0] + x, position[1] + y, *pixel);
+            }
+        }
+        if w > 0 && h > 0 {
+            // Both rectangles are inside the texture, so their ends can't overflow.
+            let rect = match self.0.dirty.get() {
+                Some(d) => {
+                    let (x0, y0) = (d[0].min(position[0]), d[1].min(position[1]));
+                    let (x1, y1) = ((d[0] + d[2]).max(position[0] + w), (d[1] + d[3]).max(position[1] + h));
+                    [x0, y0, x1 - x0, y1 - y0]
+                }
+                None => [position[0], position[1], w, h],
+            };
+            self.0.dirty.set(Some(rect));
+        }
+        Ok(())
+    }
+
+    /// Replace the pixels of the texture starting at `position` with the RGBA `pixels` of an
+    /// image which is `width` by `height` pixels, see `Texture::update()`.
+    pub fn update_rgba(&self, position: [u32; 2], width: u32, height: u32, pixels: &[u8]) -> Result<()> {
+        match img::RgbaImage::from_raw(width, height, pixels.to_vec()) {
+            Some(image) => self.update(position, &image),
+            None => bail!(ErrorKind::InvalidPixels("the buffer is too small for the size")),
+        }
+    }
+
+    /// Returns true if both textures share the same pixels.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Texture) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Read the pixels back from the GPU if the texture was created there.
+    fn read_back(&self) {
+        if self.0.image.borrow().is_some() {
+            return;
+        }
+        let raw: glium::texture::RawImage2d<u8> = match *self.0.gpu.borrow() {
+            Some(ref texture) => texture.read(),
+            None => return,
+        };
+        // Rows are read back in the order they were uploaded in, so the first row is the top one.
+        let image = img::RgbaImage::from_raw(raw.width, raw.height, raw.data.into_owned())
+            .expect("error: failed to read back texture");
+        *self.0.image.borrow_mut() = Some(image);
+    }
+
+    /// Get the GPU copy of this texture, uploading it first if this has not happened yet.
+    pub(crate) fn gpu(&self, display: &glium::Display) -> Result<Ref<'_, Texture2d>> {
+        if self.0.gpu.borrow().is_none() {
+            let image = self.0.image.borrow();
+            let image = image.as_ref().unwrap();
+            let dims = image.dimensions();
+            let texture = Texture2d::new(
+                display,
+                glium::texture::RawImage2d::from_raw_rgba(image.clone().into_raw(), dims),
+            )?;
+            *self.0.gpu.borrow_mut() = Some(Rc::new(texture));
+            self.0.dirty.set(None);
+        }
+        if let Some(d) = self.0.dirty.take() {
+            // Rows are uploaded in the order they are stored in, so the first row is at the bottom
+            // of the GPU texture.
+            let image = self.0.image.borrow();
+            let image = image.as_ref().unwrap();
+            let mut pixels = Vec::with_capacity((d[2] * d[3] * 4) as usize);
+            for y in d[1]..d[1] + d[3] {
+                for x in d[0]..d[0] + d[2] {
+                    pixels.extend_from_slice(&image.get_pixel(x, y).data);
+                }
+            }
+            let rect = glium::Rect {
+                left: d[0],
+                bottom: d[1],
+                width: d[2],
+                height: d[3],
+            };
+            if let Some(ref texture) = *self.0.gpu.borrow() {
+                texture.write(rect, glium::texture::RawImage2d::from_raw_rgba(pixels, (d[2], d[3])));
+                if texture.get_mipmap_levels() > 1 {
+                    // Only the base level is written, so the smaller levels would show the old
+                    // pixels when the texture is minified. This is safe because RGBA8 textures
+                    // can always be rendered to.
+                    unsafe { texture.generate_mipmaps() };
+                }
+            }
+        }
+        Ok(Ref::map(self.0.gpu.borrow(), |t| &**t.as_ref().unwrap()))
+    }
+}
+
+impl fmt::Debug for Texture {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Texture")
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .finish()
+    }
+}
+
+impl From<Rc<Texture2d>> for Texture {
+    #[inline]
+    fn from(texture: Rc<Texture2d>) -> Texture {
+        let key = &*texture as *const Texture2d as usize;
+        GPU_TEXTURES.with(|textures| {
+            let mut textures = textures.borrow_mut();
+            // A `TextureData` keeps its GPU texture alive, so an entry which can be upgraded is
+            // still for the same GPU texture.
+            if let Some(data) = textures.get(&key).and_then(Weak::upgrade) {
+                return Texture(data);
+            }
+            textures.retain(|_, data| data.upgrade().is_some());
+            let data = Rc::new(TextureData {
+                image: RefCell::new(None),
+                gpu: RefCell::new(Some(texture)),
+                dirty: Cell::new(None),
+            });
+            textures.insert(key, Rc::downgrade(&data));
+            Texture(data)
+        })
+    }
+}
+
+impl From<img::RgbaImage> for Texture {
+    #[inline]
+    fn from(image: img::RgbaImage) -> Texture {
+        Texture::from_image(image)
+    }
+}
+
+/// A rectangular part of a texture, such as a frame of a sprite sheet.
+///
+/// The region is stored as the texture coordinates of its top left and bottom right corners,
+/// where `[0.0, 0.0]` is the top left corner of the texture and `[1.0, 1.0]` the bottom right one.
+#[derive(Clone, Debug)]
+pub struct TextureRegion {
+    texture: Texture,
+    min: [f32; 2],
+    max: [f32; 2],
+}
+
+impl TextureRegion {
+    /// Create a region of `texture` between the texture coordinates `min` and `max`.
+    #[inline]
+    pub fn new<T: Into<Texture>>(texture: T, min: [f32; 2], max: [f32; 2]) -> TextureRegion {
+        TextureRegion {
+            texture: texture.into(),
+            min: min,
+            max: max,
+        }
+    }
+
+    /// Create a region of `texture` which is `size` pixels large and has its top left corner at
+    /// the pixel `position`.
+    pub fn from_pixels<T: Into<Texture>>(texture: T, position: [u32; 2], size: [u32; 2]) -> TextureRegion {
+        let texture = texture.into();
+        let (w, h) = (texture.width().max(1) as f32, texture.height().max(1) as f32);
+        TextureRegion {
+            min: [position[0] as f32 / w, position[1] as f32 / h],
+            max: [
+                (position[0] as f32 + size[0] as f32) / w,
+                (position[1] as f32 + size[1] as f32) / h,
+            ],
+            texture: texture,
+        }
+    }
+
+    /// The texture this is a region of.
+    #[inline]
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// The texture coordinates of the top left and bottom right corners of the region.
+    #[inline]
+    pub fn uv(&self) -> ([f32; 2], [f32; 2]) {
+        (self.min, self.max)
+    }
+
+    /// The width of the region in pixels.
+    #[inline]
+    pub fn width(&self) -> f32 {
+        let width = self.texture.width() as f32;
+        // Scale each end before subtracting so regions on whole pixels have whole sizes.
+        (self.max[0] * width - self.min[0] * width).abs()
+    }
+
+    /// The height of the region in pixels.
+    #[inline]
+    pub fn height(&self) -> f32 {
+        let height = self.texture.height() as f32;
+        (self.max[1] * height - self.min[1] * height).abs()
+    }
+}
+
+impl From<Texture> for TextureRegion {
+    #[inline]
+    fn from(texture: Texture) -> TextureRegion {
+        TextureRegion::new(texture, [0.0, 0.0], [1.0, 1.0])
+    }
+}
+
+impl From<Rc<Texture2d>> for TextureRegion {
+    #[inline]
+    fn from(texture: Rc<Texture2d>) -> TextureRegion {
+        TextureRegion::from(Texture::from(texture))
+    }
+}
+
+impl From<img::RgbaImage> for TextureRegion {
+    #[inline]
+    fn from(image: img::RgbaImage) -> TextureRegion {
+        TextureRegion::from(Texture::from_image(image))
+    }
+}
+
+/// How a texture is filtered when it is drawn larger or smaller than its size in pixels.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Filter {
+    /// Use the closest pixel, which keeps pixel art sharp.
+    Nearest,
+    /// Blend the four closest pixels.
+    Linear,
+}
+
+/// What a texture shows outside of the texture coordinates from `0.0` to `1.0`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Wrap {
+    /// Repeat the edge pixels.
+    Clamp,
+    /// Repeat the texture.
+    Repeat,
+    /// Repeat the texture, mirroring every other repetition.
+    Mirror,
+}
+
+/// Describes how the pixels of a texture are looked up when it is drawn.
+///
+/// The default filters linearly, does not use mipmaps and repeats the texture.
+///
+/// # Example
+/// ```rust,no_run
+/// use nest::*;
+/// let mut app = Window::new("Example", 640, 480).unwrap();
+/// let sprite = app.load_texture("sprite.png").unwrap();
+/// // Keep the pixels sharp.
+/// app.draw(image_w(sprite, 0.5).sampler(Sampler::nearest()));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Sampler {
+    /// The filter used when the texture is drawn larger than its size.
+    pub magnify: Filter,
+    /// The filter used when the texture is drawn smaller than its size.
+    pub minify: Filter,
+    /// Use smaller copies of the texture when it is drawn smaller, which avoids flickering.
+    /// The software `Canvas` ignores this.
+    pub mipmaps: bool,
+    /// What the texture shows outside of its texture coordinates.
+    pub wrap: Wrap,
+}
+
+impl Default for Sampler {
+    fn default() -> Sampler {
+        Sampler {
+            magnify: Filter::Linear,
+            minify: Filter::Linear,
+            mipmaps: false,
+            wrap: Wrap::Repeat,
+        }
+    }
+}
+
+impl Sampler {
+    /// A sampler which uses the closest pixel in both directions, for pixel art.
+    #[inline]
+    pub fn nearest() -> Sampler {
+        Sampler {
+            magnify: Filter::Nearest,
+            minify: Filter::Nearest,
+            ..Sampler::default()
+        }
+    }
+
+    /// Change what the texture shows outside of its texture coordinates.
+    #[inline]
+    pub fn wrap(mut self, wrap: Wrap) -> Sampler {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Use mipmaps when the texture is drawn smaller than its size.
+    #[inline]
+    pub fn mipmaps(mut self, mipmaps: bool) -> Sampler {
+        self.mipmaps = mipmaps;
+        self
+    }
+
+    /// Bind `texture` with these options for a draw call.
+    pub(crate) fn apply<'t>(&self, texture: &'t Texture2d) -> glium::uniforms::Sampler<'t, Texture2d> {
+        use glium::uniforms::{MagnifySamplerFilter, MinifySamplerFilter, SamplerWrapFunction};
+        let magnify = match self.magnify {
+            Filter::Nearest => MagnifySamplerFilter::Nearest,
+            Filter::Linear => MagnifySamplerFilter::Linear,
+        };
+        let minify = match (self.minify, self.mipmaps) {
+            (Filter::Nearest, false) => MinifySamplerFilter::Nearest,
+            (Filter::Linear, false) => MinifySamplerFilter::Linear,
+            (Filter::Nearest, true) => MinifySamplerFilter::NearestMipmapNearest,
+            (Filter::Linear, true) => MinifySamplerFilter::LinearMipmapLinear,
+        };
+        let wrap = match self.wrap {
+            Wrap::Clamp => SamplerWrapFunction::Clamp,
+            Wrap::Repeat => SamplerWrapFunction::Repeat,
+            Wrap::Mirror => SamplerWrapFunction::Mirror,
+        };
+        texture.sampled().magnify_filter(magnify).minify_filter(minify).wrap_function(wrap)
+    }
+}
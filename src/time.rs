@@ -43,3 +43,9 @@ impl ToSeconds for Duration {
         secs + subsec_nanos * 1e-9
     }
 }
+
+impl ToSeconds for f32 {
+    fn to_secs(self) -> f32 {
+        self
+    }
+}
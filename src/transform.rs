@@ -0,0 +1,208 @@
+//! Provides a 2D affine transformation matrix for moving, rotating and scaling shapes.
+
+use cgm;
+
+/// A 2D affine transformation in row form, where a point `(x, y)` becomes
+/// `(m[0][0] * x + m[0][1] * y + m[0][2], m[1][0] * x + m[1][1] * y + m[1][2])`.
+///
+/// Transformations are composed in the order they are called in, like the combinators of a
+/// `Shape`, and `Shape::translate()`, `Shape::rotate()` and the scaling combinators fold into a
+/// single `Transform` when they are nested, so every vertex is only multiplied once.
+///
+/// # Example
+/// ```rust
+/// use nest::*;
+///
+/// let t = Transform::scaling([2.0, 2.0]).translate([1.0, 0.0]);
+/// assert_eq!(t.apply([1.0, 1.0]), [3.0, 2.0].into());
+/// assert_eq!(t.inverse().unwrap().apply([3.0, 2.0]), [1.0, 1.0].into());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform(pub [[f32; 3]; 2]);
+
+impl Default for Transform {
+    fn default() -> Transform {
+        Transform::IDENTITY
+    }
+}
+
+impl Transform {
+    /// The transformation which leaves points where they are.
+    pub const IDENTITY: Transform = Transform([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+
+    /// A transformation which translates points by `vector`.
+    #[inline]
+    pub fn translation<V: Into<cgm::Vector2<f32>>>(vector: V) -> Transform {
+        let v = vector.into();
+        Transform([[1.0, 0.0, v.x], [0.0, 1.0, v.y]])
+    }
+
+    /// A transformation which rotates points around the origin by an angle in radians.
+    #[inline]
+    pub fn rotation(angle: f32) -> Transform {
+        let (sin, cos) = angle.sin_cos();
+        Transform([[cos, -sin, 0.0], [sin, cos, 0.0]])
+    }
+
+    /// A transformation which scales points away from the origin by `scale` on each axis.
+    #[inline]
+    pub fn scaling<V: Into<cgm::Vector2<f32>>>(scale: V) -> Transform {
+        let s = scale.into();
+        Transform([[s.x, 0.0, 0.0], [0.0, s.y, 0.0]])
+    }
+
+    /// A transformation which shears points, moving `x` by `shear.x * y` and `y` by `shear.y * x`.
+    #[inline]
+    pub fn shearing<V: Into<cgm::Vector2<f32>>>(shear: V) -> Transform {
+        let s = shear.into();
+        Transform([[1.0, s.x, 0.0], [s.y, 1.0, 0.0]])
+    }
+
+    /// A transformation which reflects points across the line through `a` and `b`. If the points
+    /// are the same there is no line, and points are left where they are.
+    pub fn reflection<P: Into<cgm::Point2<f32>>>(a: P, b: P) -> Transform {
+        let (a, b) = (a.into(), b.into());
+        let (dx, dy) = (b.x - a.x, b.y - a.y);
+        let len2 = dx * dx + dy * dy;
+        if !(len2 > 0.0) {
+            return Transform::IDENTITY;
+        }
+        let (cos, sin) = ((dx * dx - dy * dy) / len2, 2.0 * dx * dy / len2);
+        Transform::translation([-a.x, -a.y])
+            .then(Transform([[cos, sin, 0.0], [sin, -cos, 0.0]]))
+            .translate([a.x, a.y])
+    }
+
+    /// Translate after this transformation.
+    #[inline]
+    pub fn translate<V: Into<cgm::Vector2<f32>>>(self, vector: V) -> Transform {
+        self.then(Transform::translation(vector))
+    }
+
+    /// Rotate around the origin after this transformation.
+    #[inline]
+    pub fn rotate(self, angle: f32) -> Transform {
+        self.then(Transform::rotation(angle))
+    }
+
+    /// Scale on each axis after this transformation.
+    #[inline]
+    pub fn scale_both<V: Into<cgm::Vector2<f32>>>(self, scale: V) -> Transform {
+        self.then(Transform::scaling(scale))
+    }
+
+    /// Scale on both axes by `scale` after this transformation.
+    #[inline]
+    pub fn scale(self, scale: f32) -> Transform {
+        self.scale_both([scale, scale])
+    }
+
+    /// Rotate around `pivot` by an angle in radians after this transformation.
+    #[inline]
+    pub fn rotate_about<P: Into<cgm::Point2<f32>>>(self, pivot: P, angle: f32) -> Transform {
+        let p = pivot.into();
+        self.translate([-p.x, -p.y]).rotate(angle).translate([p.x, p.y])
+    }
+
+    /// Scale away from `pivot` by `scale` after this transformation.
+    #[inline]
+    pub fn scale_about<P: Into<cgm::Point2<f32>>>(self, pivot: P, scale: f32) -> Transform {
+        let p = pivot.into();
+        self.translate([-p.x, -p.y]).scale(scale).translate([p.x, p.y])
+    }
+
+    /// Shear after this transformation. See `Transform::shearing()`.
+    #[inline]
+    pub fn shear<V: Into<cgm::Vector2<f32>>>(self, shear: V) -> Transform {
+        self.then(Transform::shearing(shear))
+    }
+
+    /// Skew horizontally by an angle in radians after this transformation, so that vertical
+    /// lines lean to the right for positive angles.
+    #[inline]
+    pub fn skew_x(self, angle: f32) -> Transform {
+        self.shear([angle.tan(), 0.0])
+    }
+
+    /// Skew vertically by an angle in radians after this transformation, so that horizontal
+    /// lines lean upwards for positive angles.
+    #[inline]
+    pub fn skew_y(self, angle: f32) -> Transform {
+        self.shear([0.0, angle.tan()])
+    }
+
+    /// Mirror from left to right across the `y` axis after this transformation.
+    #[inline]
+    pub fn reflect_x(self) -> Transform {
+        self.scale_both([-1.0, 1.0])
+    }
+
+    /// Mirror from top to bottom across the `x` axis after this transformation.
+    #[inline]
+    pub fn reflect_y(self) -> Transform {
+        self.scale_both([1.0, -1.0])
+    }
+
+    /// Reflect across the line through `a` and `b` after this transformation. See
+    /// `Transform::reflection()`.
+    #[inline]
+    pub fn reflect_across<P: Into<cgm::Point2<f32>>>(self, a: P, b: P) -> Transform {
+        self.then(Transform::reflection(a, b))
+    }
+
+    /// The transformation which applies this one and then `next`.
+    pub fn then(self, next: Transform) -> Transform {
+        let (a, b) = (next.0, self.0);
+        let mut m = [[0.0; 3]; 2];
+        for r in 0..2 {
+            for c in 0..3 {
+                m[r][c] = a[r][0] * b[0][c] + a[r][1] * b[1][c];
+            }
+            m[r][2] += a[r][2];
+        }
+        Transform(m)
+    }
+
+    /// The transformation which applies `first` and then this one.
+    #[inline]
+    pub fn pre(self, first: Transform) -> Transform {
+        first.then(self)
+    }
+
+    /// The transformation which applies this one and then `last`. This is the same as `then()`.
+    #[inline]
+    pub fn post(self, last: Transform) -> Transform {
+        self.then(last)
+    }
+
+    /// The transformation which undoes this one, or `None` if it squashes points onto a line.
+    pub fn inverse(&self) -> Option<Transform> {
+        let m = self.0;
+        let det = m[0][0] * m[1][1] - m[0][1] * m[1][0];
+        if !(det.abs() > 0.0) || !det.is_finite() {
+            return None;
+        }
+        let (a, b, c, d) = (m[1][1] / det, -m[0][1] / det, -m[1][0] / det, m[0][0] / det);
+        Some(Transform([
+            [a, b, -(a * m[0][2] + b * m[1][2])],
+            [c, d, -(c * m[0][2] + d * m[1][2])],
+        ]))
+    }
+
+    /// Transform a point.
+    #[inline]
+    pub fn apply<P: Into<cgm::Point2<f32>>>(&self, point: P) -> cgm::Point2<f32> {
+        let (p, m) = (point.into(), self.0);
+        cgm::Point2::new(
+            m[0][0] * p.x + m[0][1] * p.y + m[0][2],
+            m[1][0] * p.x + m[1][1] * p.y + m[1][2],
+        )
+    }
+
+    /// The transformation as a column-major 3x3 matrix for the shaders.
+    #[inline]
+    pub(crate) fn matrix(&self) -> [[f32; 3]; 3] {
+        let m = self.0;
+        [[m[0][0], m[1][0], 0.0], [m[0][1], m[1][1], 0.0], [m[0][2], m[1][2], 1.0]]
+    }
+}
@@ -1,12 +1,11 @@
 use glium;
 use glium::Surface;
 use glium::glutin;
-use glium::texture::Texture2d;
-use std::io::prelude::*;
-use std::fs::File;
-use std::rc::Rc;
+use std::cell::Cell;
 use std::vec;
 use std::path;
+use std::rc::Rc;
+use glium::texture::Texture2d;
 use *;
 
 #[allow(missing_docs, unused_doc_comment)]
@@ -17,8 +16,43 @@ pub mod error {
             Image(super::img::ImageError);
             Texture(super::glium::texture::TextureCreationError);
             Program(super::glium::program::ProgramChooserCreationError);
+            Xml(super::xml::reader::Error);
+            Json(super::serde_json::Error);
+            VertexBuffer(super::glium::vertex::BufferCreationError);
             DisplayCreation(super::glium::backend::glutin::DisplayCreationError);
         }
+
+        errors {
+            InvalidPolygon(reason: &'static str) {
+                description("invalid polygon")
+                display("invalid polygon: {}", reason)
+            }
+
+            InvalidSvg(reason: String) {
+                description("invalid svg")
+                display("invalid svg: {}", reason)
+            }
+
+            InvalidFont(reason: &'static str) {
+                description("invalid font")
+                display("invalid font: {}", reason)
+            }
+
+            InvalidSpriteSheet(reason: String) {
+                description("invalid sprite sheet")
+                display("invalid sprite sheet: {}", reason)
+            }
+
+            InvalidPixels(reason: &'static str) {
+                description("invalid pixels")
+                display("invalid pixels: {}", reason)
+            }
+
+            ImageTooLarge(width: u32, height: u32) {
+                description("image does not fit into the texture atlas")
+                display("image of {}x{} pixels does not fit into the texture atlas", width, height)
+            }
+        }
     }
 }
 
@@ -44,7 +78,10 @@ pub struct Window {
     events_loop: glium::glutin::EventsLoop,
     pub(crate) texture_program: glium::Program,
     pub(crate) plain_program: glium::Program,
+    /// The texture and plain programs for instanced drawing, if the GPU supports them.
+    instanced_programs: Option<(glium::Program, glium::Program)>,
     pub(crate) clear_color: Color,
+    last_draw_calls: Cell<usize>,
 }
 
 impl Window {
@@ -89,16 +126,37 @@ impl Window {
             },
         )?;
 
+        let instanced_texture_program = program!(&display,
+            150 => {
+                vertex: include_str!("shader/instanced.vert"),
+                geometry: include_str!("shader/texture.geom"),
+                fragment: include_str!("shader/texture.frag"),
+            },
+        );
+
+        let instanced_plain_program = program!(&display,
+            150 => {
+                vertex: include_str!("shader/instanced.vert"),
+                geometry: include_str!("shader/plain.geom"),
+                fragment: include_str!("shader/plain.frag"),
+            },
+        );
+
         Ok(Window {
             display: display,
             events_loop: events_loop,
             texture_program: texture_program,
             plain_program: plain_program,
+            instanced_programs: match (instanced_texture_program, instanced_plain_program) {
+                (Ok(texture), Ok(plain)) => Some((texture, plain)),
+                _ => None,
+            },
             clear_color: Color::BLACK,
+            last_draw_calls: Cell::new(0),
         })
     }
 
-    /// Load an image from a file to be drawn by `Frame::draw_image(...)`.
+    /// Load an image from a file and upload it to the GPU so it can be drawn with `image()`.
     ///
     /// # Parameters
     ///
@@ -111,19 +169,31 @@ impl Window {
     /// let pic = app.load_image("res/city.jpg").unwrap();
     /// ```
     pub fn load_image<P: AsRef<path::Path>>(&self, path: P) -> Result<Rc<Texture2d>> {
-        let mut buf = Vec::new();
-        File::open(path)?.read_to_end(&mut buf)?;
-        let image = img::load_from_memory(&buf[..])?.to_rgba();
+        let image = Texture::open(path)?;
+        let image = image.image();
         let dims = image.dimensions();
         Ok(Rc::new(Texture2d::new(
             &self.display,
-            glium::texture::RawImage2d::from_raw_rgba(
-                image.into_raw(),
-                dims,
-            ),
+            glium::texture::RawImage2d::from_raw_rgba(image.clone().into_raw(), dims),
         )?))
     }
 
+    /// Load a `Texture` from a file and upload it to the GPU. Unlike with `load_image()`, the
+    /// pixels are kept in memory, so the texture can also be updated, packed into an atlas or
+    /// drawn on a `Canvas`. See `Texture::open()` for loading without uploading.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use nest::*;
+    /// let mut app = Window::new("Window Example", 640, 480).unwrap();
+    /// let sheet = SpriteSheet::grid(app.load_texture("walk.png").unwrap(), [32, 32]);
+    /// ```
+    pub fn load_texture<P: AsRef<path::Path>>(&self, path: P) -> Result<Texture> {
+        let texture = Texture::open(path)?;
+        texture.gpu(&self.display)?;
+        Ok(texture)
+    }
+
     /// Change the color which the screen is cleared with between frames.
     ///
     /// # Example
@@ -192,9 +262,16 @@ impl Window {
         Frame{
             target: Some(f),
             window: self,
+            draw_calls: 0,
         }
     }
 
+    /// The number of draw calls which were submitted to the GPU during the last finished frame.
+    /// See `Frame::draw_calls()`.
+    pub fn draw_calls(&self) -> usize {
+        self.last_draw_calls.get()
+    }
+
     /// Clears the frame, draws the `shape`, and updates the window.
     pub fn draw<S>(&mut self, shape: S) where S: Shape {
         let mut frame = self.frame();
@@ -207,6 +284,7 @@ impl Window {
 pub struct Frame<'a> {
     target: Option<glium::Frame>,
     window: &'a Window,
+    draw_calls: usize,
 }
 
 impl<'a> Frame<'a> {
@@ -214,52 +292,180 @@ impl<'a> Frame<'a> {
     pub fn finish(self) {}
 
     /// Draws the `shape`.
+    ///
+    /// Consecutive triangles which share the same texture and sampler are batched into a single
+    /// vertex buffer and submitted with one draw call, so the order in which triangles are painted
+    /// is preserved.
     pub fn draw<S>(&mut self, shape: S) where S: Shape {
+        let mut batch: Vec<Tri> = Vec::new();
+        let mut last: Option<RendTri> = None;
         for rtri in shape {
-            let texture = rtri.texture;
-            let vert_buff = glium::VertexBuffer::new(
-                &self.window.display,
-                &[rtri.tri],
-            ).expect("error: failed to form vertex buffer");
-            let indices = glium::index::NoIndices(glium::index::PrimitiveType::Points);
-            match texture {
-                Some(tex) => {
-                    self.target.as_mut().unwrap()
-                        .draw(
-                            &vert_buff,
-                            &indices,
-                            &self.window.texture_program,
-                            &uniform! {
-                                tex: &*tex,
-                            },
-                            &glium::DrawParameters {
-                                blend: glium::draw_parameters::Blend::alpha_blending(),
-                                ..Default::default()
-                            },
-                        )
-                        .expect("error: failed to draw");
+            if let Some(ref last) = last {
+                if !last.same_batch(&rtri) {
+                    self.flush(&batch, &last.texture, &last.sampler);
+                    batch.clear();
+                }
+            }
+            batch.push(rtri.tri);
+            last = Some(rtri);
+        }
+        if let Some(last) = last {
+            self.flush(&batch, &last.texture, &last.sampler);
+        }
+    }
+
+    /// The number of draw calls which have been submitted to the GPU so far in this frame.
+    pub fn draw_calls(&self) -> usize {
+        self.draw_calls
+    }
+
+    /// Draws a `Mesh` which has been uploaded before, moved and tinted as described by `instance`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use nest::*;
+    /// let mut app = Window::new("Window Example", 640, 480).unwrap();
+    /// let background = Mesh::new(&app, rect([-1.0, -1.0], [1.0, 1.0]).recolor(Color::BLUE)).unwrap();
+    ///
+    /// while !app.poll_events().any(|e| e == Event::Closed) {
+    ///     let mut frame = app.frame();
+    ///     frame.draw_mesh(&background);
+    ///     frame.draw_mesh(background.instance().scale(0.5).mul_color(Color::RED));
+    /// }
+    /// ```
+    pub fn draw_mesh<'m, I: Into<MeshInstance<'m>>>(&mut self, instance: I) {
+        let instance = instance.into();
+        let (matrix, tint) = (instance.instance().matrix(), instance.instance().color());
+        for batch in instance.mesh().batches() {
+            self.submit(&batch.buffer, None, &batch.texture, &batch.sampler, matrix, tint)
+                .expect("error: failed to draw");
+        }
+    }
+
+    /// Draws the `shape` once for each of the `instances`.
+    ///
+    /// The triangles of the shape are uploaded once and drawn with GPU instancing, so this is much
+    /// faster than drawing the shape many times. If the GPU does not support instancing, the
+    /// instances are transformed on the CPU and drawn like with `Frame::draw()`.
+    ///
+    /// The instances are painted one after another, like `Canvas::draw_instanced()` does. Only
+    /// shapes whose triangles all share a texture and sampler, such as a plain polygon or an
+    /// image, can be drawn with a single instanced draw call. Other shapes are uploaded once and
+    /// then drawn with one draw call for each instance and texture, so that every instance is
+    /// painted over the ones before it.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use nest::*;
+    /// let mut app = Window::new("Window Example", 640, 480).unwrap();
+    /// let stars: Vec<Instance> = (0..1000)
+    ///     .map(|i| Instance::new().translate([(i as f32 * 0.37).sin(), (i as f32 * 0.73).cos()]))
+    ///     .collect();
+    /// app.frame().draw_instanced(circle([0.0, 0.0], 0.005), &stars);
+    /// ```
+    pub fn draw_instanced<S>(&mut self, shape: S, instances: &[Instance]) where S: Shape {
+        let tris: Vec<RendTri> = shape.into_iter().collect();
+        if tris.is_empty() || instances.is_empty() {
+            return;
+        }
+        let attributes: Vec<InstanceAttributes> = instances.iter().map(InstanceAttributes::from).collect();
+        let instance_buff = match self.window.instanced_programs {
+            Some(_) => glium::VertexBuffer::new(&self.window.display, &attributes)
+                .ok()
+                .and_then(|buffer| if buffer.per_instance().is_ok() { Some(buffer) } else { None }),
+            None => None,
+        };
+        let instance_buff = match instance_buff {
+            Some(buffer) => buffer,
+            None => return self.draw(instance::expand(&tris, instances)),
+        };
+
+        let runs = instance::runs(&tris);
+        let buffers: Vec<glium::VertexBuffer<Tri>> = runs
+            .iter()
+            .map(|run| {
+                let run: Vec<Tri> = tris[run.clone()].iter().map(|t| t.tri).collect();
+                glium::VertexBuffer::new(&self.window.display, &run).expect("error: failed to form vertex buffer")
+            })
+            .collect();
+        if runs.len() == 1 {
+            let (texture, sampler) = (&tris[0].texture, tris[0].sampler);
+            self.submit(&buffers[0], Some(&instance_buff), texture, &sampler, Transform::IDENTITY.matrix(), Color::WHITE)
+                .expect("error: failed to draw");
+            return;
+        }
+        // Instancing would draw each run for all of the instances before the next run, so later
+        // parts of one instance would be painted over the earlier parts of the next one.
+        for instance in instances {
+            for (run, buffer) in runs.iter().zip(&buffers) {
+                let (texture, sampler) = (&tris[run.start].texture, tris[run.start].sampler);
+                self.submit(buffer, None, texture, &sampler, instance.matrix(), instance.color())
+                    .expect("error: failed to draw");
+            }
+        }
+    }
+
+    fn flush(&mut self, tris: &[Tri], texture: &Option<Texture>, sampler: &Sampler) {
+        let vert_buff = glium::VertexBuffer::new(&self.window.display, tris)
+            .expect("error: failed to form vertex buffer");
+        self.submit(&vert_buff, None, texture, sampler, Transform::IDENTITY.matrix(), Color::WHITE)
+            .expect("error: failed to draw");
+    }
+
+    /// Draw the triangles in `vert_buff`, once for each instance in `instances` if there are any.
+    fn submit(
+        &mut self,
+        vert_buff: &glium::VertexBuffer<Tri>,
+        instances: Option<&glium::VertexBuffer<InstanceAttributes>>,
+        texture: &Option<Texture>,
+        sampler: &Sampler,
+        transform: [[f32; 3]; 3],
+        tint: Color,
+    ) -> ::std::result::Result<(), glium::DrawError> {
+        let indices = glium::index::NoIndices(glium::index::PrimitiveType::Points);
+        let params = glium::DrawParameters {
+            blend: glium::draw_parameters::Blend::alpha_blending(),
+            ..Default::default()
+        };
+        let (texture_program, plain_program) = match (instances, self.window.instanced_programs.as_ref()) {
+            (Some(_), Some(programs)) => (&programs.0, &programs.1),
+            _ => (&self.window.texture_program, &self.window.plain_program),
+        };
+        // `draw_instanced()` only passes instances after checking that instancing is supported.
+        let per_instance = instances.map(|i| i.per_instance().expect("error: instancing is not supported"));
+        let target = self.target.as_mut().unwrap();
+        match *texture {
+            Some(ref tex) => {
+                let tex = tex.gpu(&self.window.display).expect("error: failed to upload texture");
+                let uniforms = uniform! {
+                    tex: sampler.apply(&tex),
+                    transform: transform,
+                    tint: tint.0,
+                };
+                match per_instance {
+                    Some(per_instance) => target.draw((vert_buff, per_instance), &indices, texture_program, &uniforms, &params)?,
+                    None => target.draw(vert_buff, &indices, texture_program, &uniforms, &params)?,
                 }
-                None => {
-                    self.target.as_mut().unwrap()
-                        .draw(
-                            &vert_buff,
-                            &indices,
-                            &self.window.plain_program,
-                            &glium::uniforms::EmptyUniforms,
-                            &glium::DrawParameters {
-                                blend: glium::draw_parameters::Blend::alpha_blending(),
-                                ..Default::default()
-                            },
-                        )
-                        .expect("error: failed to draw");
+            }
+            None => {
+                let uniforms = uniform! {
+                    transform: transform,
+                    tint: tint.0,
+                };
+                match per_instance {
+                    Some(per_instance) => target.draw((vert_buff, per_instance), &indices, plain_program, &uniforms, &params)?,
+                    None => target.draw(vert_buff, &indices, plain_program, &uniforms, &params)?,
                 }
             }
         }
+        self.draw_calls += 1;
+        Ok(())
     }
 }
 
 impl<'a> Drop for Frame<'a> {
     fn drop(&mut self) {
+        self.window.last_draw_calls.set(self.draw_calls);
         if let Some(t) = self.target.take() {
             t.finish().expect("error: failed to finish drawing");
         }
@@ -0,0 +1,128 @@
+//! Golden image testing for shapes.
+//!
+//! A shape is rendered with the software `Canvas` and compared to the reference PNG stored in
+//! `tests/golden/<name>.png`. When the images differ, the rendered image and a diff image are
+//! written to `target/golden/` so they can be inspected.
+//!
+//! A missing reference fails the test. References are only written, instead of compared, when
+//! the `NEST_BLESS` environment variable is set, so `NEST_BLESS=1 cargo test` creates or updates
+//! them all. Review the changed images before committing them.
+#![allow(dead_code)]
+
+use image;
+use nest::*;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Settings for rendering and comparing a golden image.
+#[derive(Copy, Clone, Debug)]
+pub struct Golden {
+    /// Width of the rendered image in pixels.
+    pub width: u32,
+    /// Height of the rendered image in pixels.
+    pub height: u32,
+    /// Color the image is cleared with before drawing.
+    pub clear_color: Color,
+    /// The largest difference allowed between any channel of a rendered and a reference pixel.
+    pub tolerance: u8,
+}
+
+impl Default for Golden {
+    fn default() -> Golden {
+        Golden {
+            width: 128,
+            height: 96,
+            clear_color: Color::BLACK,
+            tolerance: 2,
+        }
+    }
+}
+
+impl Golden {
+    /// Render `shape` and compare it to the reference image called `name`, panicking on mismatch.
+    pub fn check<S: Shape>(&self, name: &str, shape: S) {
+        let actual = render_to_image(shape, self.width, self.height, self.clear_color);
+        let reference_path = golden_dir().join(format!("{}.png", name));
+
+        if env::var_os("NEST_BLESS").is_some() {
+            fs::create_dir_all(golden_dir()).unwrap();
+            actual.save(&reference_path).unwrap();
+            return;
+        }
+        if !reference_path.exists() {
+            self.fail(name, &actual, None);
+            panic!(
+                "golden image `{}` does not exist, run the tests with `NEST_BLESS=1` to create it from {}",
+                name,
+                output_dir().join(format!("{}.actual.png", name)).display()
+            );
+        }
+
+        let expected = image::open(&reference_path)
+            .expect("error: failed to open reference image")
+            .to_rgba();
+        if expected.dimensions() != actual.dimensions() {
+            self.fail(name, &actual, None);
+            panic!(
+                "golden image `{}` is {:?} but the shape was rendered at {:?}",
+                name,
+                expected.dimensions(),
+                actual.dimensions()
+            );
+        }
+
+        let mut diff = RgbaImage::new(self.width, self.height);
+        let mut mismatched = 0;
+        for (x, y, a) in actual.enumerate_pixels() {
+            let e = expected.get_pixel(x, y);
+            let off = (0..4).any(|i| {
+                (a.data[i] as i32 - e.data[i] as i32).abs() > self.tolerance as i32
+            });
+            // Mismatched pixels are red, everything else is a faded copy of the expected image.
+            diff.put_pixel(x, y, if off {
+                mismatched += 1;
+                image::Rgba { data: [255, 0, 0, 255] }
+            } else {
+                image::Rgba { data: [e.data[0] / 4, e.data[1] / 4, e.data[2] / 4, 255] }
+            });
+        }
+
+        if mismatched != 0 {
+            self.fail(name, &actual, Some(&diff));
+            panic!(
+                "golden image `{}` differs in {} pixels with a tolerance of {}, see {}",
+                name,
+                mismatched,
+                self.tolerance,
+                output_dir().display()
+            );
+        }
+    }
+
+    fn fail(&self, name: &str, actual: &RgbaImage, diff: Option<&RgbaImage>) {
+        fs::create_dir_all(output_dir()).unwrap();
+        actual.save(output_dir().join(format!("{}.actual.png", name))).unwrap();
+        if let Some(diff) = diff {
+            diff.save(output_dir().join(format!("{}.diff.png", name))).unwrap();
+        }
+    }
+}
+
+/// Render `shape` with the default settings and compare it to the reference image called `name`.
+pub fn check<S: Shape>(name: &str, shape: S) {
+    Golden::default().check(name, shape)
+}
+
+/// Path to a file in the crate, e.g. one of the example resources.
+pub fn resource(path: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(path)
+}
+
+fn golden_dir() -> PathBuf {
+    resource("tests/golden")
+}
+
+fn output_dir() -> PathBuf {
+    resource("target/golden")
+}
@@ -0,0 +1,64 @@
+//! Golden image tests for the shapes drawn by the programs in `examples/`.
+
+extern crate image;
+extern crate nest;
+
+mod common;
+
+use nest::*;
+use std::f32::consts::PI;
+use std::iter::empty;
+
+fn flower() -> Vec<RendTri> {
+    let petal_texture = Texture::open(common::resource("examples/petal.png")).unwrap();
+    let petal = image_w(petal_texture, 0.4).translate([0.3, 0.0]);
+    (0usize..6)
+        .flat_map(|i| petal.rotate(i as f32 / 6.0 * 2.0 * PI))
+        .collect()
+}
+
+#[test]
+fn demo() {
+    // The demo rotates the flower at 1 rad/sec, so check a few points in time.
+    let flower = flower();
+    common::check("demo_0s", flower.clone());
+    common::check("demo_1s", flower.rotate(1.0));
+    common::check("demo_2_5s", flower.rotate(2.5));
+}
+
+#[test]
+fn rectangles() {
+    common::check(
+        "rectangles",
+        rect([-0.5, -0.5], [0.5, 0.5]).combine(rect([-0.8, -0.8], [0.3, 0.3])),
+    );
+}
+
+#[test]
+fn texture() {
+    let city = Texture::open(common::resource("examples/city.jpg")).unwrap();
+    common::check("texture_0s", image([0.0, 0.0], [0.5, 0.5], city.clone()));
+    common::check("texture_1s", image([0.0, 0.0], [0.5, 0.5], city).rotate(1.0));
+}
+
+#[test]
+fn thumbnail() {
+    common::Golden {
+        width: 256,
+        height: 256,
+        ..Default::default()
+    }.check("thumbnail", flower());
+}
+
+#[test]
+fn transforms() {
+    common::check(
+        "transforms",
+        rect([-0.5, -0.5], [0.5, 0.5]).combine(rect([-0.8, -0.8], [0.3, 0.3])),
+    );
+}
+
+#[test]
+fn window() {
+    common::check("window", empty());
+}
@@ -0,0 +1,536 @@
+//! Golden image tests for the shape primitives.
+
+extern crate image;
+extern crate nest;
+
+mod common;
+
+use nest::*;
+use std::f32::consts::PI;
+use std::time::Duration;
+
+#[test]
+fn ellipses() {
+    common::check(
+        "ellipses",
+        circle([-0.5, 0.4], 0.3)
+            .combine(ellipse([0.4, 0.4], [0.5, 0.2]).recolor(Color::RED))
+            .combine(circle([-0.5, -0.5], 0.3).segments(6).recolor(Color::GREEN))
+            .combine(pie([0.1, -0.5], 0.4, 0.25 * PI, 1.75 * PI).recolor(Color::YELLOW))
+            .combine(arc([0.6, -0.5], 0.25, 0.0, 1.5 * PI, 0.1).recolor(Color::CYAN)),
+    );
+
+    // The number of segments stays sane for huge circles and explicit counts.
+    assert_eq!(circle([0.0, 0.0], 100000.0).into_iter().count(), 4096);
+    assert_eq!(circle([0.0, 0.0], 0.5).segments(usize::max_value()).into_iter().count(), 4096);
+    assert_eq!(circle([0.0, 0.0], 0.5).segments(1).into_iter().count(), 3);
+}
+
+#[test]
+fn polygons() {
+    let star = (0..10)
+        .map(|i| {
+            let angle = i as f32 / 10.0 * 2.0 * PI;
+            let radius = if i % 2 == 0 { 0.6 } else { 0.25 };
+            [radius * angle.cos(), radius * angle.sin()]
+        })
+        .collect::<Vec<_>>();
+    let frame = polygon_with_holes(
+        &[[-0.9, -0.9], [0.9, -0.9], [0.9, 0.9], [-0.9, 0.9]],
+        &[&[[-0.8, -0.8], [0.8, -0.8], [0.8, 0.8], [-0.8, 0.8]][..]],
+    ).unwrap();
+    common::check(
+        "polygons",
+        frame.recolor(Color::BLUE).combine(polygon(&star).unwrap().recolor(Color::YELLOW)),
+    );
+}
+
+#[test]
+fn self_intersecting_polygon() {
+    assert!(polygon(&[[0.0, 0.0], [0.5, 0.5], [0.5, 0.0], [0.0, 0.5]]).is_err());
+}
+
+#[test]
+fn polylines() {
+    let zigzag = [[-0.8, 0.5], [-0.4, 0.8], [0.0, 0.5], [0.4, 0.8], [0.8, 0.5]];
+    let square = [[-0.3, -0.8], [0.3, -0.8], [0.3, -0.2], [-0.3, -0.2]];
+    common::check(
+        "polylines",
+        polyline(&zigzag, 0.08)
+            .combine(polyline(&zigzag, 0.08).join(Join::Round).cap(Cap::Round).translate([0.0, -0.5]).recolor(Color::RED))
+            .combine(polyline(&zigzag, 0.08).join(Join::Bevel).cap(Cap::Square).translate([0.0, -0.3]).recolor(Color::GREEN))
+            .combine(polyline(&square, 0.05).closed().recolor(Color::YELLOW)),
+    );
+
+    // Translucent lines are blended once where segments and joins meet.
+    for &join in &[Join::Miter, Join::Round, Join::Bevel] {
+        let mut canvas = Canvas::new(64, 64);
+        canvas.draw(polyline(&[[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5]], 0.2).join(join).recolor(Color::WHITE.alpha(0.5)));
+        assert_eq!(canvas.pixel(46, 46), canvas.pixel(32, 47), "{:?}", join);
+
+        let mut canvas = Canvas::new(64, 64);
+        canvas.draw(polyline(&zigzag, 0.1).join(join).cap(Cap::Round).recolor(Color::WHITE.alpha(0.5)));
+        canvas.draw(polyline(&square, 0.1).join(join).closed().recolor(Color::WHITE.alpha(0.5)));
+        let once = canvas.pixel(32, 38);
+        assert_ne!(once, [0, 0, 0, 255]);
+        assert!(canvas.image().pixels().all(|p| p.data == [0, 0, 0, 255] || p.data == once), "{:?}", join);
+    }
+    // Every segment and join is a few triangles, however long the line is.
+    let long: Vec<[f32; 2]> = (0..400).map(|i| [i as f32 / 200.0 - 1.0, (i as f32).sin() * 0.5]).collect();
+    assert!(polyline(&long, 0.01).join(Join::Round).into_iter().count() < 400 * 16);
+}
+
+#[test]
+fn paths() {
+    let heart = Path::new()
+        .move_to([0.0, -0.6])
+        .cubic_to([-0.9, 0.0], [-0.4, 0.7], [0.0, 0.3])
+        .cubic_to([0.4, 0.7], [0.9, 0.0], [0.0, -0.6])
+        .close();
+    let rings = Path::new()
+        .move_to([-0.9, 0.0])
+        .arc_to([0.4, 0.4], 0.0, false, true, [-0.1, 0.0])
+        .arc_to([0.4, 0.4], 0.0, false, true, [-0.9, 0.0])
+        .move_to([-0.7, 0.0])
+        .arc_to([0.2, 0.2], 0.0, false, true, [-0.3, 0.0])
+        .arc_to([0.2, 0.2], 0.0, false, true, [-0.7, 0.0])
+        .close();
+    common::check(
+        "paths",
+        heart.fill(FillRule::NonZero).recolor(Color::RED)
+            .combine(heart.stroke(Stroke::new(0.03)))
+            .combine(rings.fill(FillRule::EvenOdd).recolor(Color::GREEN))
+            .combine(rings.fill(FillRule::NonZero).translate([0.9, 0.0]).recolor(Color::BLUE)),
+    );
+}
+
+#[test]
+fn svg_document() {
+    let svg = Svg::parse(r##"
+        <svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 40 30">
+            <rect x="2" y="2" width="16" height="12" rx="3" fill="#0af" />
+            <g transform="translate(30 8) rotate(45)" style="fill: red; stroke: white; stroke-width: 1">
+                <rect x="-4" y="-4" width="8" height="8" />
+            </g>
+            <path d="M4 26 L12 18 l8 8 z M24 26 q6 -12 12 0 a6 3 0 0 1 -12 0" fill="yellow" fill-opacity="0.5" />
+            <circle cx="10" cy="22" r="2" fill="none" stroke="lime" />
+        </svg>
+    "##).unwrap();
+    assert!(svg.warnings().is_empty(), "{:?}", svg.warnings());
+    common::check("svg_document", svg);
+
+    // Curves are as detailed on the window whatever the size of the view box.
+    let tris = |size: f32| {
+        let source = format!(
+            r#"<svg viewBox="0 0 {0} {0}"><circle cx="{1}" cy="{1}" r="{1}" stroke="red" stroke-width="{2}" /></svg>"#,
+            size,
+            size / 2.0,
+            size / 100.0
+        );
+        Svg::parse(&source).unwrap().into_iter().count() as f32
+    };
+    assert!((tris(1000.0) / tris(10.0) - 1.0).abs() < 0.1, "{} {}", tris(1000.0), tris(10.0));
+}
+
+#[test]
+fn svg_warnings() {
+    let svg = Svg::parse(r##"
+        <svg viewBox="0 0 10 10">
+            <text>Skipped</text>
+            <rect width="5" height="5" fill="url(#gradient)" />
+            <path d="M1 1 L2 2 X" />
+        </svg>
+    "##).unwrap();
+    assert_eq!(svg.warnings().len(), 3);
+    assert!(Svg::parse("<g></g>").is_err());
+
+    // Attributes which change the drawing but are not supported are reported too.
+    let svg = Svg::parse(r##"
+        <svg viewBox="0 0 10 10" id="icon" version="1.1">
+            <g x="5" class="shadow"><circle r="2" width="3" /></g>
+        </svg>
+    "##).unwrap();
+    assert_eq!(svg.warnings().len(), 3, "{:?}", svg.warnings());
+
+    let empty = Svg::parse(r#"<svg width="0" height="10"><rect width="5" height="5" /></svg>"#).unwrap();
+    assert_eq!(empty.warnings().len(), 1);
+    assert_eq!(empty.into_iter().count(), 0);
+}
+
+#[test]
+fn svg_aspect_ratio() {
+    let bounds = |aspect: &str| {
+        let source = format!(
+            r#"<svg viewBox="0 0 20 10" {}><rect width="20" height="10" /></svg>"#,
+            aspect
+        );
+        let svg = Svg::parse(&source).unwrap();
+        assert!(svg.warnings().is_empty(), "{:?}", svg.warnings());
+        let b = svg.bounds().unwrap();
+        [b.0[0], b.0[1], b.1[0], b.1[1]]
+    };
+    assert_eq!(bounds(""), [-1.0, -0.5, 1.0, 0.5]);
+    assert_eq!(bounds(r#"preserveAspectRatio="xMinYMax""#), [-1.0, -1.0, 1.0, 0.0]);
+    assert_eq!(bounds(r#"preserveAspectRatio="none""#), [-1.0, -1.0, 1.0, 1.0]);
+    assert_eq!(bounds(r#"preserveAspectRatio="xMinYMin slice""#), [-1.0, -1.0, 3.0, 1.0]);
+}
+
+#[test]
+fn svg_export() {
+    let shape = rect([-0.5, -0.5], [0.5, 0.5])
+        .recolor(Color::RED)
+        .combine(circle([0.5, 0.5], 0.25).segments(8).recolor(Color::BLUE.alpha(0.5)));
+    let document = svg_string(shape.clone(), 64, 64).unwrap();
+    assert!(document.contains("fill=\"#0000ff\" opacity=\"0.5\""));
+
+    // Exported documents load back without losing anything.
+    let loaded = Svg::parse(&document).unwrap();
+    assert!(loaded.warnings().is_empty(), "{:?}", loaded.warnings());
+    assert!(loaded.into_iter().count() >= shape.into_iter().count());
+
+    let texture = Texture::from_image(RgbaImage::from_pixel(2, 2, image::Rgba { data: [0, 255, 0, 255] }));
+    let document = svg_string(image_w(texture.clone(), 0.5).combine(image_w(texture.clone(), 0.2)), 64, 64).unwrap();
+    assert_eq!(document.matches("data:image/png;base64,").count(), 1);
+    assert!(document.contains("patternTransform=\"matrix("));
+    // Both triangles of each image share a pattern, and white images aren't filtered.
+    assert_eq!(document.matches("<pattern id=\"fill").count(), 2);
+    assert!(!document.contains("<filter"));
+
+    // Recolored textures are multiplied by a filter which is shared like the patterns.
+    let tinted = image_w(texture, 0.5).mul_color(Color::RED);
+    let document = svg_string(tinted.clone().combine(tinted), 64, 64).unwrap();
+    assert_eq!(document.matches("<feColorMatrix type=\"matrix\" values=\"1 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 0\"").count(), 1);
+    assert_eq!(document.matches("filter=\"url(#tint0)\"").count(), 4);
+    assert_eq!(document.matches("<pattern id=\"fill").count(), 1);
+}
+
+#[test]
+fn text_layout() {
+    let font = Font::open(common::resource("examples/DejaVuSansMono.ttf")).unwrap();
+    common::check(
+        "text_layout",
+        text(&font, "Left\nAV", 0.2).translate([-0.9, 0.7])
+            .combine(text(&font, "Center", 0.2).align(Align::Center).recolor(Color::YELLOW))
+            .combine(text(&font, "wrapped words here", 0.15).wrap(0.8).align(Align::Right).translate([0.9, -0.3])),
+    );
+    assert_eq!(text(&font, " \n ", 0.2).into_iter().count(), 0);
+    // Large glyphs fill the atlas, which is then replaced by a taller one.
+    let alphabet: String = (b'!'..b'~').map(|b| b as char).collect();
+    assert_eq!(text(&font.resolution(300.0), &alphabet, 0.2).into_iter().count(), alphabet.len() * 2);
+    // Glyphs wider than the atlas make it wider instead of being cut off.
+    let width = |font: &Font| {
+        let b = text(font, "W", 0.5).bounds().unwrap();
+        b.1[0] - b.0[0]
+    };
+    assert!((width(&font.resolution(2000.0)) / width(&font) - 1.0).abs() < 0.05);
+    assert!(Font::from_bytes(vec![0; 16]).is_err());
+}
+
+#[test]
+fn bitmap_text_layout() {
+    // Two 4x4 cells: a filled square for `#` and a hollow one for `o`.
+    let sheet = RgbaImage::from_fn(8, 4, |x, y| {
+        let hollow = x >= 4 && x % 4 != 0 && x % 4 != 3 && y != 0 && y != 3;
+        image::Rgba { data: if hollow { [0, 0, 0, 0] } else { [255, 255, 255, 255] } }
+    });
+    let font = BitmapFont::new(Texture::from_image(sheet), [4, 4], '#').char_map("#o");
+    common::check(
+        "bitmap_text",
+        bitmap_text(&font, "#o#\no o", 0.2).align(Align::Center).recolor(Color::GREEN),
+    );
+    assert_eq!(bitmap_text(&font, "?", 0.2).into_iter().count(), 0);
+}
+
+#[test]
+fn image_orientation() {
+    // Red, green, blue and white pixels in the top left, top right, bottom left and bottom right.
+    let texture = Texture::from_rgba(2, 2, vec![
+        255, 0, 0, 255, 0, 255, 0, 255,
+        0, 0, 255, 255, 255, 255, 255, 255,
+    ]).unwrap();
+    let corners = |shape: Image| {
+        let mut canvas = Canvas::new(4, 4);
+        canvas.draw(shape.sampler(Sampler::nearest()));
+        [canvas.pixel(0, 0), canvas.pixel(3, 0), canvas.pixel(0, 3), canvas.pixel(3, 3)]
+    };
+    let upright = [[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255], [255, 255, 255, 255]];
+    assert_eq!(corners(image([-1.0, -1.0], [1.0, 1.0], texture.clone())), upright);
+    assert_eq!(corners(image_w(texture.clone(), 2.0)), upright);
+    let region = TextureRegion::from_pixels(texture, [0, 0], [2, 2]);
+    assert_eq!(corners(image_region([-1.0, -1.0], [1.0, 1.0], region)), upright);
+}
+
+#[test]
+fn sprite_sheets() {
+    // Four 2x2 frames in a row, colored red, green, blue and white.
+    let colors = [[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255], [255, 255, 255, 255]];
+    let texture = Texture::from_image(RgbaImage::from_fn(8, 2, |x, _| image::Rgba { data: colors[x as usize / 2] }));
+
+    let grid = SpriteSheet::grid(texture.clone(), [2, 2]);
+    assert_eq!(grid.len(), 4);
+
+    let json = r#"{
+        "frames": [
+            { "filename": "blue", "frame": { "x": 4, "y": 0, "w": 2, "h": 2 }, "rotated": false },
+            { "filename": "red", "frame": { "x": 0, "y": 0, "w": 2, "h": 2 } }
+        ],
+        "meta": { "size": { "w": 8, "h": 2 } }
+    }"#;
+    let sheet = SpriteSheet::from_json(texture.clone(), json).unwrap();
+    assert_eq!(sheet.len(), 2);
+    assert_eq!(sheet.get("red").unwrap().uv(), ([0.0, 0.0], [0.25, 1.0]));
+    assert!(SpriteSheet::from_json(texture.clone(), r#"{ "frames": { "a": { "rotated": true } } }"#).is_err());
+    let frame = |frame: &str| SpriteSheet::from_json(texture.clone(), &format!(r#"{{ "frames": [{}] }}"#, frame));
+    assert!(frame(r#"{ "frame": { "x": 0, "y": 0, "w": 2, "h": 2 }, "trimmed": true }"#).is_err());
+    assert!(frame(r#"{ "frame": { "x": 4294967295, "y": 0, "w": 2, "h": 2 } }"#).is_err());
+    assert!(frame(r#"{ "frame": { "x": 6, "y": 0, "w": 4, "h": 2 } }"#).is_err());
+
+    common::check(
+        "sprite_sheets",
+        image_region([-0.9, -0.4], [-0.5, 0.0], grid.frame(1).unwrap().clone())
+            .combine(image_region([-0.4, -0.4], [0.0, 0.0], sheet.get("blue").unwrap().clone()))
+            .combine(image_region([0.1, -0.4], [0.9, 0.4], TextureRegion::from_pixels(texture, [2, 0], [4, 2]))),
+    );
+}
+
+#[test]
+fn animations() {
+    let texture = Texture::from_image(RgbaImage::new(4, 1));
+    let sheet = SpriteSheet::grid(texture, [1, 1]);
+    let frames = sheet.frames().iter().cloned();
+    let animation = Animation::from_frames(frames, 0.5);
+    let at = |animation: &Animation, times: &[f32]| times.iter().map(|&t| animation.index(t).unwrap()).collect::<Vec<_>>();
+
+    let times = [0.0, 0.6, 1.9, 2.0, 2.6, 3.1, 4.2];
+    assert_eq!(at(&animation, &times), [0, 1, 3, 0, 1, 2, 0]);
+    assert_eq!(at(&animation.clone().mode(LoopMode::Once), &times), [0, 1, 3, 3, 3, 3, 3]);
+    assert_eq!(at(&animation.clone().mode(LoopMode::PingPong), &times), [0, 1, 3, 2, 1, 0, 2]);
+    assert_eq!(animation.clone().mode(LoopMode::PingPong).duration(), 3.0);
+    assert!(animation.clone().mode(LoopMode::Once).is_finished(Duration::from_secs(2)));
+    assert!(Animation::new().region(1.0).is_none());
+}
+
+#[test]
+fn nine_slices() {
+    // A 6x6 panel with a 2 pixel red border, a green stripe through the middle and a blue center.
+    let panel = Texture::from_image(RgbaImage::from_fn(6, 6, |x, y| {
+        let border = x < 2 || y < 2 || x >= 4 || y >= 4;
+        image::Rgba { data: if border { [255, 0, 0, 255] } else if x == 2 { [0, 255, 0, 255] } else { [0, 0, 255, 255] } }
+    }));
+    common::check(
+        "nine_slices",
+        nine_slice([-0.9, -0.8], [-0.1, 0.8], panel.clone(), [2, 2, 2, 2]).scale(0.05)
+            .combine(nine_slice([0.1, -0.8], [0.9, 0.8], panel, [2, 2, 2, 2]).scale(0.05).fill(SliceFill::Tile)),
+    );
+}
+
+#[test]
+fn texture_atlas() {
+    let mut atlas = TextureAtlas::new().max_size(16);
+    let red = atlas.add(RgbaImage::from_pixel(6, 4, image::Rgba { data: [255, 0, 0, 255] }));
+    let green = atlas.add(RgbaImage::from_pixel(8, 8, image::Rgba { data: [0, 255, 0, 255] }));
+    let blue = atlas.add(RgbaImage::from_pixel(12, 10, image::Rgba { data: [0, 0, 255, 255] }));
+    let regions = atlas.build().unwrap();
+    assert_eq!(regions.len(), 3);
+    assert_eq!((regions[red].width(), regions[red].height()), (6.0, 4.0));
+    // The blue image fills most of a texture, so the others go into a second one.
+    assert!(!regions[blue].texture().ptr_eq(regions[green].texture()));
+    assert!(regions[red].texture().ptr_eq(regions[green].texture()));
+
+    let mut small = TextureAtlas::new().max_size(8);
+    small.add(RgbaImage::new(8, 2));
+    assert!(small.build().is_err());
+
+    common::check(
+        "texture_atlas",
+        image_region([-0.9, -0.5], [-0.4, 0.5], regions[red].clone())
+            .combine(image_region([-0.3, -0.5], [0.2, 0.5], regions[green].clone()))
+            .combine(image_region([0.3, -0.5], [0.8, 0.5], regions[blue].clone())),
+    );
+}
+
+#[test]
+fn texture_updates() {
+    let texture = Texture::from_rgba(4, 4, vec![0; 64]).unwrap();
+    assert!(Texture::from_rgba(4, 4, vec![0; 63]).is_err());
+    assert!(Texture::from_bytes(&[1, 2, 3]).is_err());
+
+    let shape = image([-0.5, -0.5], [0.5, 0.5], texture.clone());
+    texture.update_rgba([0, 0], 2, 2, &[255; 16]).unwrap();
+    texture.update([2, 2], &RgbaImage::from_pixel(2, 2, image::Rgba { data: [0, 0, 255, 255] })).unwrap();
+    assert!(texture.update([3, 3], &RgbaImage::new(2, 2)).is_err());
+    assert!(texture.update([u32::max_value(), 0], &RgbaImage::new(2, 2)).is_err());
+    assert!(texture.update([0, u32::max_value() - 1], &RgbaImage::new(2, 2)).is_err());
+    // Shapes which were created before the update draw the new pixels.
+    common::check("texture_updates", shape);
+}
+
+#[test]
+fn samplers() {
+    // A 2x2 checkerboard drawn with texture coordinates from -1 to 2, which shows it 3 times.
+    let checker = Texture::from_image(RgbaImage::from_fn(2, 2, |x, y| {
+        image::Rgba { data: if (x + y) % 2 == 0 { [255, 255, 255, 255] } else { [255, 0, 0, 255] } }
+    }));
+    let tile = |sampler: Sampler, x: f32| {
+        image_region([0.0, 0.0], [0.4, 0.4], TextureRegion::new(checker.clone(), [-1.0, -1.0], [2.0, 2.0]))
+            .sampler(sampler)
+            .translate([x, -0.2])
+    };
+    common::check(
+        "samplers",
+        tile(Sampler::default(), -0.9)
+            .combine(tile(Sampler::nearest(), -0.4))
+            .combine(tile(Sampler::nearest().wrap(Wrap::Clamp), 0.1))
+            .combine(tile(Sampler::nearest().wrap(Wrap::Mirror), 0.6)),
+    );
+}
+
+#[test]
+fn texture_fills() {
+    let checker = Texture::from_image(RgbaImage::from_fn(2, 2, |x, y| {
+        image::Rgba { data: if (x + y) % 2 == 0 { [255, 255, 0, 255] } else { [0, 0, 255, 255] } }
+    }));
+    // Both shapes line up with the same grid, whatever their size.
+    common::check(
+        "texture_fills",
+        rect([-0.9, -0.9], [-0.1, 0.3])
+            .combine(circle([0.5, 0.2], 0.4))
+            .fill_texture(checker.clone(), [0.2, 0.2], [0.0, 0.0])
+            .sampler(Sampler::nearest())
+            .combine(rect([0.1, -0.9], [0.9, -0.5]).fill_texture(checker, [0.4, 0.2], [0.1, -0.5]).rotate(0.1)),
+    );
+}
+
+#[test]
+fn uv_combinators() {
+    // The left half is red and the right half is green, with a white pixel at the top left.
+    let texture = Texture::from_image(RgbaImage::from_fn(4, 4, |x, y| {
+        image::Rgba { data: if x == 0 && y == 0 { [255, 255, 255, 255] } else if x < 2 { [255, 0, 0, 255] } else { [0, 255, 0, 255] } }
+    }));
+    let tile = |x: f32, y: f32| image([x, y], [x + 0.3, y + 0.3], texture.clone()).sampler(Sampler::nearest());
+    common::check(
+        "uv_combinators",
+        tile(-0.9, 0.1)
+            .combine(tile(-0.5, 0.1).flip_x())
+            .combine(tile(-0.1, 0.1).flip_y())
+            .combine(tile(0.3, 0.1).translate_uv([0.25, 0.0]))
+            .combine(tile(-0.9, -0.4).scale_uv([2.0, 2.0]))
+            .combine(tile(-0.5, -0.4).rotate_uv(0.3)),
+    );
+
+    // Flipping a region of a texture mirrors it in place instead of showing the rest of the texture.
+    let strip = Texture::from_rgba(4, 1, vec![255, 255, 255, 255, 255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255]).unwrap();
+    let region = TextureRegion::from_pixels(strip, [0, 0], [2, 1]);
+    let mut canvas = Canvas::new(2, 1);
+    canvas.draw(image_region([-1.0, -1.0], [1.0, 1.0], region).flip_x().sampler(Sampler::nearest()));
+    assert_eq!([canvas.pixel(0, 0), canvas.pixel(1, 0)], [[255, 0, 0, 255], [255, 255, 255, 255]]);
+
+    // Every glyph of a text is mirrored in place.
+    let font = Font::open(common::resource("examples/DejaVuSansMono.ttf")).unwrap();
+    common::check(
+        "text_flips",
+        text(&font, "Flip", 0.3).translate([-0.5, 0.3])
+            .combine(text(&font, "Flip", 0.3).flip_x().translate([-0.5, -0.1]))
+            .combine(text(&font, "Flip", 0.3).flip_y().translate([-0.5, -0.5])),
+    );
+}
+
+#[test]
+fn instances() {
+    let shape = || rect([-0.1, -0.1], [0.1, 0.1]).recolor(Color::GREEN);
+    let instances = [
+        Instance::new(),
+        Instance::new().translate([-0.5, 0.3]),
+        Instance::new().scale(2.0).rotate(0.5).translate([0.5, -0.3]),
+        Instance::new().scale_both([3.0, 0.5]).mul_color(Color::RED).translate([0.0, -0.7]),
+    ];
+    let mut canvas = Canvas::new(128, 96);
+    canvas.draw_instanced(shape(), &instances);
+
+    // Instances transform a shape like the matching combinators.
+    let expected = shape()
+        .combine(shape().translate([-0.5, 0.3]))
+        .combine(shape().scale(2.0).rotate(0.5).translate([0.5, -0.3]))
+        .combine(shape().scale_both([3.0, 0.5]).mul_color(Color::RED).translate([0.0, -0.7]));
+    assert!(canvas.image().pixels().zip(render_to_image(expected.clone(), 128, 96, Color::BLACK).pixels())
+        .all(|(a, b)| a.data.iter().zip(b.data.iter()).all(|(a, b)| (*a as i16 - *b as i16).abs() <= 1)));
+    common::check("instances", expected);
+
+    // Every part of an instance is painted before the next instance, so the square of the second
+    // instance covers the image of the first one.
+    let white = Texture::from_image(RgbaImage::from_pixel(1, 1, image::Rgba { data: [255; 4] }));
+    let shape = rect([-0.5, -0.5], [0.5, 0.5]).recolor(Color::BLUE)
+        .combine(image([-0.2, -0.2], [0.2, 0.2], white));
+    let mut canvas = Canvas::new(64, 64);
+    canvas.draw_instanced(shape, &[Instance::new(), Instance::new().translate([0.6, 0.0])]);
+    assert_eq!(canvas.pixel(36, 32), [0, 0, 255, 255]);
+}
+
+#[test]
+fn transforms() {
+    let t = Transform::rotation(0.5).translate([0.2, -0.1]).scale_both([2.0, 0.5]);
+    let p = t.inverse().unwrap().apply(t.apply([0.3, 0.7]));
+    assert!((p.x - 0.3).abs() < 1e-5 && (p.y - 0.7).abs() < 1e-5);
+    assert_eq!(Transform::scaling([2.0, 2.0]).then(Transform::translation([1.0, 0.0])).apply([1.0, 1.0]), [3.0, 2.0].into());
+    assert_eq!(Transform::scaling([2.0, 2.0]).pre(Transform::translation([1.0, 0.0])).apply([1.0, 1.0]), [4.0, 2.0].into());
+    assert_eq!(Transform::IDENTITY.post(t), t);
+    assert_eq!(Transform::scaling([0.0, 1.0]).inverse(), None);
+
+    // Nested transformations fold into a single combinator.
+    let shape = rect([-0.2, -0.2], [0.2, 0.2]);
+    let nested = shape.translate([0.3, 0.0]).rotate(0.5).scale_x(2.0).scale_y(0.8).scale(0.9).translate([-0.4, 0.1]);
+    assert_eq!(std::mem::size_of_val(&nested), std::mem::size_of_val(&shape.rotate(0.5)));
+    common::check("transform_folding", nested.combine(shape.recolor(Color::RED).rotate(1.0).translate([0.6, 0.5])));
+}
+
+#[test]
+fn rotations_and_scaling() {
+    common::check(
+        "rotations_and_scaling",
+        rect([-0.5, -0.5], [0.5, 0.5])
+            .rotate(0.3)
+            .combine(rect([-0.8, -0.8], [0.3, 0.3]).scale_both([0.5, 1.0]).mul_color(Color::RED))
+            .combine(rect([0.2, 0.2], [0.6, 0.6]).scale(1.5).rotate(-0.4).mul_color([0.0, 1.0, 0.0, 0.5])),
+    );
+}
+
+#[test]
+fn skews_and_reflections() {
+    let t = Transform::IDENTITY.reflect_across([0.0, 0.0], [1.0, 1.0]);
+    let p = t.apply([0.5, 0.2]);
+    assert!((p.x - 0.2).abs() < 1e-6 && (p.y - 0.5).abs() < 1e-6);
+    let p = Transform::IDENTITY.skew_x(PI / 4.0).apply([0.0, 1.0]);
+    assert!((p.x - 1.0).abs() < 1e-6 && (p.y - 1.0).abs() < 1e-6);
+    assert_eq!(Transform::reflection([0.3, 0.3], [0.3, 0.3]), Transform::IDENTITY);
+
+    let tri = polygon(&[[0.0, 0.0], [0.4, 0.0], [0.0, 0.3]]).unwrap();
+    common::check(
+        "skews_and_reflections",
+        tri.translate([-0.8, 0.3])
+            .combine(tri.recolor(Color::RED).skew_x(0.5).translate([-0.2, 0.3]))
+            .combine(tri.recolor(Color::GREEN).skew_y(0.5).shear([0.2, 0.0]).translate([0.4, 0.3]))
+            .combine(tri.recolor(Color::BLUE).reflect_x().translate([-0.3, -0.6]))
+            .combine(tri.recolor(Color::YELLOW).reflect_y().translate([0.0, -0.2]))
+            .combine(tri.recolor(Color::CYAN).translate([0.4, -0.6]).reflect_across([0.5, -1.0], [1.0, 0.0]))
+            .combine(tri.transform(Transform::rotation(0.3).scale(0.5)).mul_color(Color::MAGENTA)),
+    );
+}
+
+#[test]
+fn pivots() {
+    let square = rect([0.2, 0.2], [0.6, 0.4]);
+    assert!(rect([0.0, 0.0], [0.0, 0.0]).translate([1.0, 1.0]).skew_x(0.5).bounds().is_some());
+    assert!(Vec::<RendTri>::new().bounds().is_none());
+    let b = square.rotate_about([0.4, 0.3], PI).bounds().unwrap();
+    assert!((b.0[0] - 0.2).abs() < 1e-5 && (b.1[1] - 0.4).abs() < 1e-5);
+    let b = square.scale_about_anchor(Anchor::BottomLeft, 2.0).bounds().unwrap();
+    assert!((b.0[0] - 0.2).abs() < 1e-5 && (b.0[1] - 0.2).abs() < 1e-5 && (b.1[0] - 1.0).abs() < 1e-5);
+
+    common::check(
+        "pivots",
+        square.translate([-0.8, 0.0]).rotate_about_anchor(Anchor::Center, 0.5)
+            .combine(square.recolor(Color::RED).rotate_about_anchor(Anchor::TopLeft, -0.5))
+            .combine(square.recolor(Color::GREEN).translate([-0.6, -0.9]).scale_about_anchor(Anchor::BottomCenter, 1.5))
+            .combine(square.recolor(Color::BLUE).translate([0.0, -0.8]).scale_about([0.4, -0.5], 0.5)),
+    );
+}